@@ -8,27 +8,37 @@ mod url;
 
 use crate::contract::body::BodyTokens;
 use crate::contract::headers::HeaderTokens;
+pub(crate) use crate::contract::query::is_nested;
 use crate::contract::query::QueryTokens;
+pub(crate) use crate::contract::request::parse_timeout;
 use crate::contract::request::RequestTokens;
 use crate::contract::response::ResponseTokens;
 use crate::contract::url::UrlTokens;
 use crate::meta::PatMetas;
-use crate::util::{ExprArg, StrArg, StrTuple};
-use proc_macro2::{Ident, TokenStream, TokenTree};
+use crate::symbol::Symbol;
+use crate::util::{get_expr_path, get_name_value, unknown_argument, ExprArg, StrArg, StrTuple};
+use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use std::collections::HashSet;
 use syn::parse::{Parse, ParseStream};
 use syn::{
-    bracketed, parse_quote, Attribute, Error, FnArg, LitStr, Pat, Signature, Token, TraitItemFn,
+    bracketed, parse_quote, AttrStyle, Attribute, Error, Expr, ExprLit, FnArg, Lit, LitStr, Meta,
+    Pat, Path, RangeLimits, Signature, Token, TraitItemFn,
 };
 
 #[derive(Default, Debug)]
 pub(crate) struct Contract {
-    pub method: Option<Ident>,
+    pub method: Option<LitStr>,
     pub path: Option<LitStr>,
     pub headers: Option<Vec<(LitStr, LitStr)>>,
-    pub serialized: Option<Ident>,
-    pub deserialized: Option<Ident>,
+    pub serialized: Option<Path>,
+    pub deserialized: Option<Path>,
+    pub query_encoding: Option<Ident>,
+    pub error: Option<Path>,
+    pub error_body: Option<Path>,
+    pub problem_details: bool,
+    pub accept_status: Option<Vec<(u16, u16)>>,
+    pub timeout: Option<LitStr>,
 }
 
 impl Parse for Contract {
@@ -37,8 +47,20 @@ impl Parse for Contract {
         let mut contract = Contract::default();
         while !input.is_empty() {
             let lookahead = input.lookahead1();
-            if lookahead.peek(kw::path) {
+            if lookahead.peek(kw::method) {
                 if contract.method.is_some() {
+                    return Err(input.error("duplicate attribute `method`"));
+                }
+                let method = input.parse::<StrArg<kw::method>>()?;
+                if !is_valid_method(&method.value.value()) {
+                    return Err(Error::new_spanned(
+                        &method.value,
+                        "invalid http method, expected a non-empty token",
+                    ));
+                }
+                contract.method = Some(method.value);
+            } else if lookahead.peek(kw::path) {
+                if contract.path.is_some() {
                     return Err(input.error("duplicate attribute `path`"));
                 }
                 let path = input.parse::<StrArg<kw::path>>()?;
@@ -55,7 +77,7 @@ impl Parse for Contract {
                 }
                 let serialized = input
                     .parse::<ExprArg<kw::serialized>>()
-                    .and_then(|serialized| serialized.require_ident())?;
+                    .and_then(|serialized| serialized.require_path())?;
                 contract.serialized = Some(serialized);
             } else if lookahead.peek(kw::deserialized) {
                 if contract.deserialized.is_some() {
@@ -63,12 +85,57 @@ impl Parse for Contract {
                 }
                 let deserialized = input
                     .parse::<ExprArg<kw::deserialized>>()
-                    .and_then(|deserialized| deserialized.require_ident())?;
+                    .and_then(|deserialized| deserialized.require_path())?;
                 contract.deserialized = Some(deserialized);
-            } else if lookahead.peek(Token![,]) {
-                let _ = input.parse::<Token![,]>()?;
+            } else if lookahead.peek(kw::query_encoding) {
+                if contract.query_encoding.is_some() {
+                    return Err(input.error("duplicate attribute `query_encoding`"));
+                }
+                let query_encoding = input
+                    .parse::<ExprArg<kw::query_encoding>>()
+                    .and_then(|query_encoding| query_encoding.require_ident())?;
+                let _ = is_nested(&query_encoding)?;
+                contract.query_encoding = Some(query_encoding);
+            } else if lookahead.peek(kw::error) {
+                if contract.error.is_some() {
+                    return Err(input.error("duplicate attribute `error`"));
+                }
+                let error = input
+                    .parse::<ExprArg<kw::error>>()
+                    .and_then(|error| error.require_path())?;
+                contract.error = Some(error);
+            } else if lookahead.peek(kw::error_body) {
+                if contract.error_body.is_some() {
+                    return Err(input.error("duplicate attribute `error_body`"));
+                }
+                let error_body = input
+                    .parse::<ExprArg<kw::error_body>>()
+                    .and_then(|error_body| error_body.require_path())?;
+                contract.error_body = Some(error_body);
+            } else if lookahead.peek(kw::problem_details) {
+                if contract.problem_details {
+                    return Err(input.error("duplicate attribute `problem_details`"));
+                }
+                let _ = input.parse::<kw::problem_details>()?;
+                contract.problem_details = true;
+            } else if lookahead.peek(kw::accept_status) {
+                if contract.accept_status.is_some() {
+                    return Err(input.error("duplicate attribute `accept_status`"));
+                }
+                let accept_status = input.parse::<AcceptStatus>()?;
+                contract.accept_status = Some(accept_status.0);
+            } else if lookahead.peek(kw::timeout) {
+                if contract.timeout.is_some() {
+                    return Err(input.error("duplicate attribute `timeout`"));
+                }
+                let timeout = input.parse::<StrArg<kw::timeout>>()?;
+                let _ = parse_timeout(&timeout.value)?;
+                contract.timeout = Some(timeout.value);
             } else {
-                let _ = input.parse::<TokenTree>()?;
+                return Err(unknown_argument(input, kw::KEYWORDS));
+            }
+            if !input.is_empty() {
+                let _ = input.parse::<Token![,]>()?;
             }
         }
         Ok(contract)
@@ -83,6 +150,12 @@ impl Contract {
             headers,
             serialized,
             deserialized,
+            query_encoding,
+            error,
+            error_body,
+            problem_details,
+            accept_status,
+            timeout,
         } = self;
         let TraitItemFn {
             mut attrs,
@@ -108,35 +181,123 @@ impl Contract {
         }
 
         let method =
-            method.ok_or_else(|| Error::new_spanned(&sig, "missing attribute: `method`"))?;
-        let path = path
-            .map(|path| path.value())
-            .ok_or_else(|| Error::new_spanned(&sig, "missing attribute: `path`"))?;
+            method.ok_or_else(|| Error::new_spanned(&sig.ident, "missing attribute: `method`"))?;
+        let path =
+            path.ok_or_else(|| Error::new_spanned(&sig.ident, "missing attribute: `path`"))?;
 
         let metas = PatMetas::new(&sig.inputs)?;
         let url_tokens = UrlTokens::new(&metas, &path)?;
-        let request_tokens = RequestTokens::new(method);
-        let query_tokens = QueryTokens::new(&metas);
+        let timeout = match timeout {
+            Some(timeout) => Some(timeout),
+            None => get_timeout(&attrs)?,
+        };
+        let request_tokens =
+            RequestTokens::new(method, timeout.as_ref().map(parse_timeout).transpose()?);
+        let query_tokens =
+            QueryTokens::new(&metas, &attrs, url_tokens.query_params(), query_encoding)?;
         let header_tokens = HeaderTokens::new(&metas, headers.unwrap_or_default());
         let body_tokens = BodyTokens::new(&metas, &attrs, serialized)?;
-        let response_tokens = ResponseTokens::new(&attrs, deserialized)?;
+        let contract = get_contract_name(&attrs, &sig.ident);
+        let problem_details = problem_details || has_path_attr(&attrs, symbol::PROBLEM_DETAILS);
+        let response_tokens = ResponseTokens::new(
+            contract,
+            &attrs,
+            &sig.output,
+            deserialized,
+            error_body,
+            problem_details,
+            accept_status.unwrap_or_default(),
+        )?;
+        let accept_tokens = response_tokens.accept_tokens();
+        let error = match error {
+            Some(error) => Some(error),
+            None => get_error(&attrs)?,
+        };
 
         reformat(&mut sig, &mut attrs, &metas);
 
+        let block = quote! {
+            #url_tokens
+            #request_tokens
+            #accept_tokens
+            #query_tokens
+            #header_tokens
+            #body_tokens
+            #response_tokens
+        };
+        // convert the `hadorn::Error` into the custom error
+        let block = match error {
+            Some(error) => quote! {
+                let __result: hadorn::Result<_> = async { #block }.await;
+                __result.map_err(<#error as hadorn::FromError>::from_error)
+            },
+            None => block,
+        };
         Ok(quote! {
             #(#attrs)*
             #sig {
-                #url_tokens
-                #request_tokens
-                #query_tokens
-                #header_tokens
-                #body_tokens
-                #response_tokens
+                #block
             }
         })
     }
 }
 
+///
+/// the contract method name with the trait name: `Users::create`
+///
+fn get_contract_name(attrs: &[Attribute], ident: &Ident) -> String {
+    let resource =
+        get_name_value(attrs, symbol::RESOURCE).and_then(|name_value| match &name_value.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => Some(lit.value()),
+            _ => None,
+        });
+    match resource {
+        Some(resource) => format!("{}::{}", resource, ident),
+        None => ident.to_string(),
+    }
+}
+
+fn has_path_attr(attrs: &[Attribute], name: Symbol) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.style == AttrStyle::Outer)
+        .any(|attr| attr.path() == name && matches!(attr.meta, Meta::Path(_)))
+}
+
+fn get_error(attrs: &[Attribute]) -> syn::Result<Option<Path>> {
+    match get_name_value(attrs, symbol::ERROR)
+        .cloned()
+        .map(|name_value| name_value.value)
+    {
+        None => Ok(None),
+        Some(expr) => get_expr_path(&expr)
+            .ok_or_else(|| Error::new_spanned(&expr, "invalid attribute: `error`"))
+            .map(Some),
+    }
+}
+
+fn get_timeout(attrs: &[Attribute]) -> syn::Result<Option<LitStr>> {
+    match get_name_value(attrs, symbol::TIMEOUT) {
+        None => Ok(None),
+        Some(name_value) => match &name_value.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(timeout),
+                ..
+            }) => Ok(Some(timeout.clone())),
+            value => Err(Error::new_spanned(value, "invalid attribute: `timeout`")),
+        },
+    }
+}
+
+fn is_valid_method(method: &str) -> bool {
+    !method.is_empty()
+        && method
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
 fn reformat(sig: &mut Signature, attrs: &mut Vec<Attribute>, metas: &PatMetas) {
     reformat_optional_input(sig, metas);
     reformat_input_attrs(sig);
@@ -200,11 +361,109 @@ impl Parse for Headers {
     }
 }
 
+///
+/// the accepted statuses and the status ranges: `accept_status = [404, 409, 500..=599]`
+///
+struct AcceptStatus(Vec<(u16, u16)>);
+
+impl Parse for AcceptStatus {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let _ = input.parse::<kw::accept_status>()?;
+        let _ = input.parse::<Token![=]>()?;
+        let content;
+        let _ = bracketed!(content in input);
+        let accept_status = content
+            .parse_terminated(Expr::parse, Token![,])?
+            .iter()
+            .map(get_status_range)
+            .collect::<syn::Result<Vec<_>>>()?;
+        Ok(Self(accept_status))
+    }
+}
+
+///
+/// convert the status `404` or the range `500..=599` / `400..500` to the inclusive range
+///
+fn get_status_range(expr: &Expr) -> syn::Result<(u16, u16)> {
+    let (start, end) = match expr {
+        Expr::Range(range) => {
+            let (Some(start), Some(end)) = (&range.start, &range.end) else {
+                return Err(Error::new_spanned(
+                    range,
+                    "attribute `accept_status` only supports bounded ranges",
+                ));
+            };
+            let start = get_status(start)?;
+            let end = get_status(end)?;
+            match range.limits {
+                RangeLimits::Closed(_) => (start, end),
+                RangeLimits::HalfOpen(_) => (start, end.saturating_sub(1)),
+            }
+        }
+        _ => {
+            let status = get_status(expr)?;
+            (status, status)
+        }
+    };
+    if start > end {
+        return Err(Error::new_spanned(expr, "empty status range"));
+    }
+    Ok((start, end))
+}
+
+fn get_status(expr: &Expr) -> syn::Result<u16> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => match lit.base10_parse::<u16>() {
+            Ok(status) if (100..=999).contains(&status) => Ok(status),
+            _ => Err(Error::new_spanned(
+                lit,
+                "invalid http status, expected 100..=999",
+            )),
+        },
+        _ => Err(Error::new_spanned(
+            expr,
+            "attribute `accept_status` only supports integer statuses and ranges",
+        )),
+    }
+}
+
+mod symbol {
+    use crate::symbol::Symbol;
+
+    pub const RESOURCE: Symbol = Symbol("resource");
+    pub const ERROR: Symbol = Symbol("error");
+    pub const PROBLEM_DETAILS: Symbol = Symbol("problem_details");
+    pub const TIMEOUT: Symbol = Symbol("timeout");
+}
+
 mod kw {
     use syn::custom_keyword;
 
+    custom_keyword!(method);
     custom_keyword!(path);
     custom_keyword!(headers);
     custom_keyword!(serialized);
     custom_keyword!(deserialized);
+    custom_keyword!(query_encoding);
+    custom_keyword!(error);
+    custom_keyword!(error_body);
+    custom_keyword!(problem_details);
+    custom_keyword!(accept_status);
+    custom_keyword!(timeout);
+
+    pub const KEYWORDS: &[&str] = &[
+        "method",
+        "path",
+        "headers",
+        "serialized",
+        "deserialized",
+        "query_encoding",
+        "error",
+        "error_body",
+        "problem_details",
+        "accept_status",
+        "timeout",
+    ];
 }
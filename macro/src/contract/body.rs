@@ -1,71 +1,99 @@
 use crate::meta::{Kind, PatMetas};
-use crate::util::{get_expr_ident, get_name_value};
-use proc_macro2::{Ident, Span, TokenStream};
+use crate::util::{get_expr_path, get_name_value};
+use proc_macro2::{Ident, TokenStream};
 use quote::{quote, ToTokens};
-use syn::{Attribute, Error};
+use syn::{parse_quote, Attribute, Error, Path};
 
 pub struct BodyTokens {
     body: Option<Ident>,
-    serialized: Option<Ident>,
+    optional: bool,
+    encoder: Option<Path>,
+    multipart: bool,
 }
 
 impl BodyTokens {
     pub fn new(
         metas: &PatMetas,
         attrs: &[Attribute],
-        serialized: Option<Ident>,
+        serialized: Option<Path>,
     ) -> syn::Result<Self> {
-        let body = metas
-            .iter()
-            .find(|meta| meta.kind == Kind::Body)
-            .map(|meta| &meta.ident)
-            .cloned();
+        let meta = metas.iter().find(|meta| meta.kind == Kind::Body);
+        let body = meta.map(|meta| &meta.ident).cloned();
+        let optional = meta.is_some_and(|meta| meta.optional);
         let serialized = match serialized {
             Some(serialized) => Some(serialized),
             None => get_serialized(attrs)?,
         };
-        Ok(Self { body, serialized })
+        let multipart = serialized
+            .as_ref()
+            .and_then(Path::get_ident)
+            .is_some_and(|ident| ident == symbol::MULTIPART);
+        let encoder = serialized.map(get_encoder);
+        Ok(Self {
+            body,
+            optional,
+            encoder,
+            multipart,
+        })
+    }
+
+    fn body_tokens(&self, body: &Ident) -> TokenStream {
+        match &self.encoder {
+            // the `Multipart` encoder is expanded by `hadorn`, a disabled feature reports a single error
+            Some(_) if self.multipart => quote! {
+                let __request = hadorn::__multipart_body!(__request, #body);
+            },
+            Some(encoder) => quote! {
+                let __request = <#encoder as hadorn::Encoder<_>>::encode(__request, #body)?;
+            },
+            None => quote! {
+                let __request = __request.body(#body);
+            },
+        }
     }
 }
 
 impl ToTokens for BodyTokens {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let stream = self.body.as_ref().map(|body| match &self.serialized {
-            Some(serialized) => {
-                if serialized == symbol::JSON {
-                    quote! {
-                        let __request = __request.json(&#body);
-                    }
-                } else if serialized == symbol::FORM {
-                    quote! {
-                        let __request = __request.form(&#body);
-                    }
-                } else if serialized == symbol::MULTIPART {
-                    quote! {
-                        let __request = __request.multipart(#body);
-                    }
-                } else {
-                    quote! {
-                        let __request = __request.body(#body);
-                    }
-                }
+        let stream = self.body.as_ref().map(|body| {
+            let stream = self.body_tokens(body);
+            match self.optional {
+                true => quote! {
+                    let __request = match #body {
+                        Some(#body) => {
+                            #stream
+                            __request
+                        }
+                        None => __request,
+                    };
+                },
+                false => stream,
             }
-            None => quote! {
-                let __request = __request.body(#body);
-            },
         });
         tokens.extend(stream);
     }
 }
 
-fn get_serialized(attrs: &[Attribute]) -> syn::Result<Option<Ident>> {
+///
+/// resolve the built-in encoder names to the `hadorn::codec` types, others are the user types
+///
+fn get_encoder(serialized: Path) -> Path {
+    match serialized.get_ident() {
+        Some(ident) if symbol::ENCODERS.iter().any(|encoder| ident == encoder) => {
+            parse_quote!(hadorn::codec::#ident)
+        }
+        _ => serialized,
+    }
+}
+
+fn get_serialized(attrs: &[Attribute]) -> syn::Result<Option<Path>> {
     match get_name_value(attrs, symbol::SERIALIZED)
         .cloned()
         .map(|name_value| name_value.value)
     {
         None => Ok(None),
-        Some(expr) => get_expr_ident(&expr)
-            .ok_or_else(|| Error::new(Span::call_site(), "invalid attribute: `serialized`"))
+        Some(expr) => get_expr_path(&expr)
+            .ok_or_else(|| Error::new_spanned(&expr, "invalid attribute: `serialized`"))
             .map(Some),
     }
 }
@@ -74,7 +102,14 @@ mod symbol {
     use crate::symbol::Symbol;
 
     pub const SERIALIZED: Symbol = Symbol("serialized");
-    pub const JSON: Symbol = Symbol("Json");
-    pub const FORM: Symbol = Symbol("Form");
     pub const MULTIPART: Symbol = Symbol("Multipart");
+    pub const ENCODERS: [Symbol; 7] = [
+        Symbol("Json"),
+        Symbol("Form"),
+        Symbol("Multipart"),
+        Symbol("Xml"),
+        Symbol("MsgPack"),
+        Symbol("Cbor"),
+        Symbol("Protobuf"),
+    ];
 }
@@ -50,12 +50,17 @@ impl ToTokens for HeaderTokens<'_> {
             .iter()
             .filter(|meta| meta.kind == Kind::Header)
             .map(|meta| {
-                let header_name = match &meta.rename {
-                    None => meta.ident.to_string(),
-                    Some(rename) => rename.value(),
-                };
-                let header_value = &meta.ident;
-                quote! { let __request = __request.header(#header_name, #header_value); }
+                let header_name = meta.name();
+                let header_value = meta.value();
+                match meta.absent() {
+                    true => quote! {
+                        let __request = match #header_value {
+                            Some(value) => __request.header(#header_name, value),
+                            None => __request,
+                        };
+                    },
+                    false => quote! { let __request = __request.header(#header_name, #header_value); },
+                }
             });
         tokens.extend(stream);
     }
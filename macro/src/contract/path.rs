@@ -1,22 +1,52 @@
 use crate::meta::{Kind, PatMetas};
 use proc_macro2::Span;
-use std::collections::HashSet;
-use syn::Error;
-
-pub struct PathParams(Vec<String>);
-
-impl PathParams {
-    pub fn new(path: &str) -> Self {
-        let start = path.find("://").unwrap_or(0);
-        let end = path.find("?").unwrap_or(path.len());
-        let params = path[start..end]
-            .split('/')
-            .filter(|item| item.starts_with('<') && item.ends_with('>'))
-            .flat_map(|item| item.strip_prefix('<'))
-            .flat_map(|item| item.strip_suffix('>'))
-            .map(|item| item.to_string())
-            .collect();
-        Self(params)
+use syn::{Error, LitStr};
+
+///
+/// the parsed path template, example: `/repos/<owner>/<repo>/contents/<path..>?ref=<branch>`
+///
+pub struct PathTemplate {
+    path: Vec<Part>,
+    query: Vec<Vec<Part>>,
+    span: Span,
+}
+
+pub enum Part {
+    Literal(String),
+    Param(Param),
+}
+
+pub struct Param {
+    pub name: String,
+    pub catch_all: bool,
+}
+
+impl PathTemplate {
+    pub fn parse(path: &LitStr) -> syn::Result<Self> {
+        let span = path.span();
+        let value = path.value();
+        let (path, query) = match value.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (value.as_str(), None),
+        };
+        let path = parse_parts(path, span)?;
+        let query = query
+            .map(|query| {
+                query
+                    .split('&')
+                    .filter(|pair| !pair.is_empty())
+                    .map(|pair| parse_parts(pair, span))
+                    .collect::<syn::Result<Vec<_>>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+        if let Some(param) = query.iter().flat_map(|pair| params(pair)).find(|param| param.catch_all) {
+            return Err(Error::new(
+                span,
+                format!("catch-all param `<{}..>` not allowed in query", param.name),
+            ));
+        }
+        Ok(Self { path, query, span })
     }
 
     pub fn check(self, metas: &PatMetas) -> syn::Result<Self> {
@@ -25,22 +55,42 @@ impl PathParams {
             .map(|_| self)
     }
 
+    ///
+    /// the params in the path part, only bind to the `#[path]` arguments
+    ///
+    pub fn path_params(&self) -> impl Iterator<Item = &Param> {
+        params(&self.path)
+    }
+
+    ///
+    /// the params in the query part, bind to the `#[path]` or `#[query]` arguments
+    ///
+    pub fn query_params(&self) -> impl Iterator<Item = &Param> {
+        self.query.iter().flat_map(|pair| params(pair))
+    }
+
+    pub fn query_pairs(&self) -> &[Vec<Part>] {
+        &self.query
+    }
+
     fn check_undefined_params(&self, metas: &PatMetas) -> syn::Result<()> {
-        let metas = metas
-            .iter()
-            .filter(|meta| meta.kind == Kind::Path)
-            .map(|meta| match &meta.rename {
-                Some(rename) => rename.value(),
-                None => meta.ident.to_string(),
-            })
-            .collect::<HashSet<_>>();
-        self.0
-            .iter()
-            .try_fold((), |_, param| match metas.contains(param) {
+        let is_defined = |param: &Param, kinds: &[Kind]| {
+            metas
+                .iter()
+                .filter(|meta| kinds.contains(&meta.kind))
+                .any(|meta| meta.name() == param.name)
+        };
+        self.path_params()
+            .map(|param| (param, [Kind::Path].as_slice()))
+            .chain(
+                self.query_params()
+                    .map(|param| (param, [Kind::Path, Kind::Query].as_slice())),
+            )
+            .try_fold((), |_, (param, kinds)| match is_defined(param, kinds) {
                 true => Ok(()),
                 false => Err(Error::new(
-                    Span::call_site(),
-                    format!("undefined path param: `{}`", param),
+                    self.span,
+                    format!("undefined path param: `{}`", param.name),
                 )),
             })
     }
@@ -50,41 +100,94 @@ impl PathParams {
             .iter()
             .filter(|meta| meta.kind == Kind::Path)
             .try_fold((), |_, meta| {
-                let param = match &meta.rename {
-                    None => meta.ident.to_string(),
-                    Some(rename) => rename.value(),
-                };
-                match self.0.contains(&param) {
+                let param = meta.name();
+                match self
+                    .path_params()
+                    .chain(self.query_params())
+                    .any(|item| item.name == param)
+                {
                     true => Ok(()),
-                    false => Err(Error::new(
-                        Span::call_site(),
+                    false => Err(Error::new_spanned(
+                        &meta.ident,
                         format!("missing path param: `{}`", param),
                     )),
                 }
             })
     }
 
-    pub fn get_format_pattern(path: &str) -> String {
-        let mut pattern = String::with_capacity(path.len());
-        let start = path.find("://").unwrap_or(0);
-        let end = path.find("?").unwrap_or(path.len());
-        if start > 0 {
-            pattern.push_str(&path[..start]);
+    pub fn get_format_pattern(&self) -> String {
+        get_format_pattern(&self.path)
+    }
+
+    pub fn get_literal(&self) -> String {
+        get_literal(&self.path)
+    }
+}
+
+impl Param {
+    fn parse(param: &str, span: Span) -> syn::Result<Self> {
+        let (name, catch_all) = match param.strip_suffix("..") {
+            Some(name) => (name, true),
+            None => (param, false),
+        };
+        if name.is_empty() || name.contains(|c: char| c.is_whitespace() || "</?#&=".contains(c)) {
+            return Err(Error::new(
+                span,
+                format!("invalid path param: `<{}>`", param),
+            ));
         }
-        path[start..end]
-            .split('/')
-            .filter(|item| !item.is_empty())
-            .for_each(|item| {
-                pattern.push('/');
-                if item.starts_with('<') && item.ends_with('>') {
-                    pattern.push_str("{}");
-                } else {
-                    pattern.push_str(item);
-                }
-            });
-        if end < path.len() {
-            pattern.push_str(&path[end..]);
+        Ok(Self {
+            name: name.to_string(),
+            catch_all,
+        })
+    }
+}
+
+fn parse_parts(template: &str, span: Span) -> syn::Result<Vec<Part>> {
+    let mut parts = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find('<') {
+        if start > 0 {
+            parts.push(Part::Literal(rest[..start].to_string()));
         }
-        pattern
+        let end = rest[start..]
+            .find('>')
+            .map(|end| start + end)
+            .ok_or_else(|| Error::new(span, "unclosed path param, expected `>`"))?;
+        parts.push(Part::Param(Param::parse(&rest[start + 1..end], span)?));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Literal(rest.to_string()));
     }
+    Ok(parts)
+}
+
+pub fn params(parts: &[Part]) -> impl Iterator<Item = &Param> {
+    parts.iter().filter_map(|part| match part {
+        Part::Literal(_) => None,
+        Part::Param(param) => Some(param),
+    })
+}
+
+///
+/// the literal string of the parts without params, the braces are not escaped
+///
+pub fn get_literal(parts: &[Part]) -> String {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            Part::Literal(literal) => Some(literal.as_str()),
+            Part::Param(_) => None,
+        })
+        .collect()
+}
+
+pub fn get_format_pattern(parts: &[Part]) -> String {
+    let mut pattern = String::new();
+    parts.iter().for_each(|part| match part {
+        Part::Literal(literal) => pattern.push_str(&literal.replace('{', "{{").replace('}', "}}")),
+        Part::Param(_) => pattern.push_str("{}"),
+    });
+    pattern
 }
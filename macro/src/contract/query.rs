@@ -1,25 +1,81 @@
-use crate::meta::{Kind, PatMetas};
-use proc_macro2::{Span, TokenStream};
+use crate::meta::{is_collection, Kind, PatMeta, PatMetas, QueryStyle};
+use crate::util::{get_expr_ident, get_name_value};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
-use syn::{Lifetime, Type};
+use std::collections::HashSet;
+use syn::{Attribute, Error, Expr, Lifetime, Lit, Type};
 
 const LIFETIME: &str = "'a";
 
-pub struct QueryTokens<'a>(&'a PatMetas);
+pub struct QueryTokens<'a> {
+    metas: &'a PatMetas,
+    bound: HashSet<String>,
+    style: QueryStyle,
+    nested: bool,
+}
 
 impl<'a> QueryTokens<'a> {
-    pub fn new(metas: &'a PatMetas) -> Self {
-        Self(metas)
+    ///
+    /// the `bound` params are set by the path template, skip them in the query struct
+    ///
+    pub fn new(
+        metas: &'a PatMetas,
+        attrs: &[Attribute],
+        bound: HashSet<String>,
+        query_encoding: Option<Ident>,
+    ) -> syn::Result<Self> {
+        if let Some(meta) = metas
+            .iter()
+            .filter(|meta| meta.kind == Kind::Query && (meta.style.is_some() || meta.flatten))
+            .find(|meta| bound.contains(&meta.name()))
+        {
+            return Err(Error::new_spanned(
+                &meta.ident,
+                "attribute `style` or `flatten` not supported by the param bound in the path template",
+            ));
+        }
+        let style = get_query_style(attrs)?.unwrap_or(QueryStyle::Form);
+        let query_encoding = match query_encoding {
+            Some(query_encoding) => Some(query_encoding),
+            None => get_query_encoding(attrs)?,
+        };
+        let nested = match query_encoding {
+            Some(query_encoding) => is_nested(&query_encoding)?,
+            None => false,
+        };
+        Ok(Self {
+            metas,
+            bound,
+            style,
+            nested,
+        })
+    }
+
+    ///
+    /// the collection style of the argument, `None` if the argument is a scalar value
+    ///
+    fn get_style(&self, meta: &PatMeta) -> Option<QueryStyle> {
+        match meta.style {
+            Some(style) => Some(style),
+            None => match !self.nested && !meta.flatten && is_collection(&meta.ty) {
+                true => Some(self.style),
+                false => None,
+            },
+        }
     }
 }
 
 impl ToTokens for QueryTokens<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let metas = self
-            .0
+            .metas
             .iter()
             .filter(|meta| meta.kind == Kind::Query)
+            .filter(|meta| !self.bound.contains(&meta.name()))
             .collect::<Vec<_>>();
+        let (styled, metas) = metas
+            .into_iter()
+            .partition::<Vec<_>, _>(|meta| self.get_style(meta).is_some());
 
         // query struct
         let stream = match metas.is_empty() {
@@ -34,16 +90,19 @@ impl ToTokens for QueryTokens<'_> {
                     if let Type::Reference(reference) = ty.as_mut() {
                         reference.lifetime = lifetime.clone();
                     }
-                    let ty = match meta.optional {
+                    let ty = match meta.absent() {
                         true => quote! { Option<#ty> },
                         false => quote! { #ty },
                     };
-                    let serde_rename = meta
-                        .rename
-                        .as_ref()
-                        .map(|rename| quote! { #[serde(rename = #rename)] });
+                    let serde_attr = match meta.flatten {
+                        true => Some(quote! { #[serde(flatten)] }),
+                        false => meta
+                            .rename
+                            .as_ref()
+                            .map(|rename| quote! { #[serde(rename = #rename)] }),
+                    };
                     quote! {
-                        #serde_rename
+                        #serde_attr
                         #ident: #ty,
                     }
                 });
@@ -66,13 +125,81 @@ impl ToTokens for QueryTokens<'_> {
             false => {
                 let fields = metas.iter().map(|meta| {
                     let ident = &meta.ident;
-                    quote! { #ident }
+                    let value = meta.value();
+                    quote! { #ident: #value }
                 });
-                Some(quote! {
-                    let __request = __request.query(&__Query { #(#fields),* });
+                let query = quote! { __Query { #(#fields),* } };
+                Some(match self.nested {
+                    true => quote! {
+                        hadorn::__require_qs!();
+                        let __request = __request.query(&hadorn::__private::NestedQuery(&#query));
+                    },
+                    false => quote! {
+                        let __request = __request.query(&#query);
+                    },
                 })
             }
         };
         tokens.extend(stream);
+
+        // set collection query
+        let stream = styled.iter().map(|meta| {
+            let value = meta.value();
+            let name = meta.name();
+            let style = self.get_style(meta).unwrap_or(self.style).ident();
+            quote! {
+                let __request = __request.query(&hadorn::__private::StyledQuery::new(
+                    #name,
+                    &#value,
+                    hadorn::__private::QueryStyle::#style,
+                ));
+            }
+        });
+        tokens.extend(stream);
     }
 }
+
+fn get_query_style(attrs: &[Attribute]) -> syn::Result<Option<QueryStyle>> {
+    match get_name_value(attrs, symbol::QUERY_STYLE).map(|name_value| &name_value.value) {
+        None => Ok(None),
+        Some(Expr::Lit(lit)) => match &lit.lit {
+            Lit::Str(style) => QueryStyle::parse(style).map(Some),
+            lit => Err(Error::new_spanned(lit, "invalid attribute: `query_style`")),
+        },
+        Some(expr) => Err(Error::new_spanned(expr, "invalid attribute: `query_style`")),
+    }
+}
+
+fn get_query_encoding(attrs: &[Attribute]) -> syn::Result<Option<Ident>> {
+    match get_name_value(attrs, symbol::QUERY_ENCODING).map(|name_value| &name_value.value) {
+        None => Ok(None),
+        Some(expr) => get_expr_ident(expr)
+            .ok_or_else(|| Error::new_spanned(expr, "invalid attribute: `query_encoding`"))
+            .map(Some),
+    }
+}
+
+///
+/// check the query encoding is `Nested` or `Flat`
+///
+pub fn is_nested(query_encoding: &Ident) -> syn::Result<bool> {
+    if query_encoding == symbol::NESTED {
+        Ok(true)
+    } else if query_encoding == symbol::FLAT {
+        Ok(false)
+    } else {
+        Err(Error::new_spanned(
+            query_encoding,
+            "invalid query encoding, expected one of: `Flat`, `Nested`",
+        ))
+    }
+}
+
+mod symbol {
+    use crate::symbol::Symbol;
+
+    pub const QUERY_STYLE: Symbol = Symbol("query_style");
+    pub const QUERY_ENCODING: Symbol = Symbol("query_encoding");
+    pub const FLAT: Symbol = Symbol("Flat");
+    pub const NESTED: Symbol = Symbol("Nested");
+}
@@ -1,24 +1,98 @@
 use crate::util::reqwest_mod_path;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
+use std::time::Duration;
+use syn::{Error, Ident, LitStr};
+
+const STANDARD_METHODS: [&str; 9] = [
+    "GET", "POST", "PUT", "DELETE", "HEAD", "OPTIONS", "CONNECT", "PATCH", "TRACE",
+];
 
 pub struct RequestTokens {
-    method: Ident,
+    method: LitStr,
+    timeout: Option<Duration>,
 }
 
 impl RequestTokens {
-    pub fn new(method: Ident) -> Self {
-        Self { method }
+    pub fn new(method: LitStr, timeout: Option<Duration>) -> Self {
+        Self { method, timeout }
     }
 }
 
+///
+/// parse the request timeout: `500ms`、`30s`、`5m`、`1h`, the units can be combined: `1m30s`
+///
+pub(crate) fn parse_timeout(timeout: &LitStr) -> syn::Result<Duration> {
+    let invalid = || {
+        Error::new_spanned(
+            timeout,
+            "invalid timeout, expected the numbers with the units `ms`, `s`, `m`, `h`: `30s`, `1m30s`",
+        )
+    };
+    let value = timeout.value();
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+    let mut duration = Duration::ZERO;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let number = rest[..digits].parse::<u64>().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+        let units = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let millis = match &rest[..units] {
+            "ms" => 1,
+            "s" => 1000,
+            "m" => 60 * 1000,
+            "h" => 60 * 60 * 1000,
+            _ => return Err(invalid()),
+        };
+        rest = &rest[units..];
+        let millis = number.checked_mul(millis).ok_or_else(invalid)?;
+        duration = duration
+            .checked_add(Duration::from_millis(millis))
+            .ok_or_else(invalid)?;
+    }
+    if duration.is_zero() {
+        return Err(Error::new_spanned(
+            timeout,
+            "the timeout must be greater than zero",
+        ));
+    }
+    Ok(duration)
+}
+
 impl ToTokens for RequestTokens {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let reqwest_mod = reqwest_mod_path();
-        let method = &self.method;
+        let method = self.method.value();
+        let method = match STANDARD_METHODS.contains(&method.as_str()) {
+            true => {
+                let method = Ident::new(&method, Span::call_site());
+                quote! { #reqwest_mod::Method::#method }
+            }
+            false => {
+                let method = &self.method;
+                quote! {
+                    #reqwest_mod::Method::from_bytes(#method.as_bytes()).expect("invalid http method")
+                }
+            }
+        };
         let stream = quote! {
-            let __request = self.client().request(#reqwest_mod::Method::#method, __url);
+            let __method = #method;
+            let __request = self.client().request(__method.clone(), __url);
         };
         tokens.extend(stream);
+
+        if let Some(timeout) = self.timeout {
+            let millis = timeout.as_millis() as u64;
+            tokens.extend(quote! {
+                let __request = __request.timeout(::std::time::Duration::from_millis(#millis));
+            });
+        }
     }
 }
@@ -1,67 +1,410 @@
-use crate::util::{get_expr_ident, get_name_value};
-use proc_macro2::{Ident, Span, TokenStream};
-use quote::{quote, ToTokens};
-use syn::{Attribute, Error};
+use crate::util::{get_expr_path, get_name_value};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_quote, AttrStyle, Attribute, Error, Expr, GenericArgument, Lit, LitStr, Path,
+    PathArguments, ReturnType, Type,
+};
 
 pub struct ResponseTokens {
-    deserialized: Option<Ident>,
+    contract: String,
+    decoder: Option<Path>,
+    wrapped: bool,
+    headers: Vec<ResponseHeader>,
+    error_body: Option<(Path, Path)>,
+    problem_details: bool,
+    accept_status: Vec<(u16, u16)>,
+    optional: bool,
+    some: bool,
+}
+
+///
+/// the response header bound to the return tuple element: `#[response_header = "x-total-count"]`
+///
+struct ResponseHeader {
+    name: LitStr,
+    ty: Type,
+    optional: bool,
 }
 
 impl ResponseTokens {
-    pub fn new(attrs: &[Attribute], deserialized: Option<Ident>) -> syn::Result<Self> {
-        let deserialized = match deserialized {
-            Some(deserialized) => Some(deserialized),
-            None => get_deserialized(attrs)?,
+    ///
+    /// the decoder priority: method `deserialized` > inferred from the return type > resource `deserialized` > `Json`
+    ///
+    pub fn new(
+        contract: String,
+        attrs: &[Attribute],
+        output: &ReturnType,
+        deserialized: Option<Path>,
+        error_body: Option<Path>,
+        problem_details: bool,
+        mut accept_status: Vec<(u16, u16)>,
+    ) -> syn::Result<Self> {
+        // `Result<Option<T>>`, the `404` and `204` statuses map to `None`, others decode `T` into `Some`
+        let ok_type = get_ok_type(output);
+        let optional_type = ok_type.and_then(get_optional_type);
+        let optional = optional_type.is_some();
+        if optional {
+            accept_status.push((404, 404));
+        }
+        let value_type = optional_type.or(ok_type);
+        let wrapped_type = value_type.and_then(get_wrapped_type);
+        let wrapped = wrapped_type.is_some();
+        let (body_type, headers) =
+            get_response_headers(attrs, output, wrapped_type.or(value_type))?;
+        let error_body = match error_body {
+            Some(error_body) => Some(error_body),
+            None => get_error_body(attrs)?,
+        };
+        // the error body decoder: method `deserialized` > resource `deserialized` > `Json`
+        let error_body = match error_body {
+            Some(error_body) => {
+                let deserialized = match &deserialized {
+                    Some(deserialized) => deserialized.clone(),
+                    None => get_deserialized(attrs)?.unwrap_or_else(|| parse_quote!(Json)),
+                };
+                Some((get_decoder(deserialized), error_body))
+            }
+            None => None,
         };
-        Ok(Self { deserialized })
+        let decoder = match deserialized {
+            Some(deserialized) => Some(get_decoder(deserialized)),
+            None => match infer_decoder(body_type) {
+                Inferred::Ignore => None,
+                Inferred::Decoder(decoder) => Some(decoder),
+                Inferred::Default => {
+                    let deserialized =
+                        get_deserialized(attrs)?.unwrap_or_else(|| parse_quote!(Json));
+                    Some(get_decoder(deserialized))
+                }
+            },
+        };
+        // the serde decoders decode the body into `Option<T>`, so a `null` body is `None` too
+        let some = optional
+            && (wrapped || !headers.is_empty() || !decoder.as_ref().is_some_and(is_serde_decoder));
+        Ok(Self {
+            contract,
+            decoder,
+            wrapped,
+            headers,
+            error_body,
+            problem_details,
+            accept_status,
+            optional,
+            some,
+        })
+    }
+
+    ///
+    /// bind the decoder and set the `Accept` header, the static headers can replace it
+    ///
+    pub fn accept_tokens(&self) -> TokenStream {
+        match &self.decoder {
+            Some(decoder) => quote! {
+                let __decoder = hadorn::__private::decoder::<#decoder, _>();
+                let __request = __decoder.accept(__request);
+            },
+            None => TokenStream::new(),
+        }
     }
 }
 
 impl ToTokens for ResponseTokens {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        let contract = &self.contract;
+        let problem_details = self.problem_details;
+        let accept_status = self
+            .accept_status
+            .iter()
+            .map(|(start, end)| quote! { (#start, #end) });
+        let check = quote! {
+            hadorn::__private::StatusCheck {
+                method: __method,
+                contract: #contract,
+                problem_details: #problem_details,
+                accept_status: &[#(#accept_status),*],
+            }
+        };
+        let check_status = match &self.error_body {
+            Some((decoder, error_body)) => quote! {
+                hadorn::__private::check_status_with_body::<#decoder, #error_body>(__response, #check)
+            },
+            None => quote! {
+                hadorn::__private::check_status(__response, #check)
+            },
+        };
         let stream = quote! {
-            let __response = __request.send().await.and_then(|response| response.error_for_status())?;
+            let __response = __request.send().await?;
+            let __response = #check_status.await?;
         };
         tokens.extend(stream);
 
-        let stream = match self.deserialized.as_ref() {
-            Some(deserialized) => {
-                if deserialized == symbol::JSON {
-                    quote! {
-                        __response.json().await
-                    }
-                } else if deserialized == symbol::TEXT {
-                    quote! {
-                        __response.text().await
-                    }
-                } else if deserialized == symbol::BYTES {
-                    quote! {
-                        __response.bytes().await
-                    }
-                } else if deserialized == symbol::RESPONSE {
-                    quote! {
-                        Ok(__response)
-                    }
-                } else {
-                    quote! {
-                        Ok(())
-                    }
+        if self.optional {
+            tokens.extend(quote! {
+                if matches!(
+                    __response.status(),
+                    hadorn::__http::StatusCode::NOT_FOUND | hadorn::__http::StatusCode::NO_CONTENT
+                ) {
+                    return Ok(None);
+                }
+            });
+        }
+
+        let header_idents = (0..self.headers.len())
+            .map(|index| format_ident!("__header_{}", index))
+            .collect::<Vec<_>>();
+        let stream = self
+            .headers
+            .iter()
+            .zip(&header_idents)
+            .map(|(header, ident)| {
+                let ResponseHeader { name, ty, optional } = header;
+                let parse = match optional {
+                    true => quote! { optional_response_header },
+                    false => quote! { response_header },
+                };
+                quote! {
+                    let #ident = hadorn::__private::#parse::<#ty>(__response.headers(), #name)?;
                 }
+            });
+        tokens.extend(stream);
+
+        if self.wrapped {
+            tokens.extend(quote! {
+                let __parts = hadorn::__private::ResponseParts::new(&__response);
+            });
+        }
+
+        let body = match &self.decoder {
+            Some(_) => {
+                tokens.extend(quote! {
+                    let __body = __decoder.decode(__response).await?;
+                });
+                quote! { __body }
             }
-            None => quote! { Ok(()) },
+            None => quote! { () },
+        };
+        let value = match header_idents.is_empty() {
+            true => body,
+            false => quote! { (#body, #(#header_idents),*) },
+        };
+        let value = match self.wrapped {
+            true => quote! { __parts.into_response(#value) },
+            false => value,
+        };
+        let stream = match self.some {
+            true => quote! { Ok(Some(#value)) },
+            false => quote! { Ok(#value) },
         };
         tokens.extend(stream);
     }
 }
 
-fn get_deserialized(attrs: &[Attribute]) -> syn::Result<Option<Ident>> {
+enum Inferred {
+    /// `()`, ignore the response body
+    Ignore,
+    /// the decoder inferred from the return type
+    Decoder(Path),
+    /// the other types, use the default decoder
+    Default,
+}
+
+///
+/// infer the decoder from the `Ok` type of the return type: `Result<T, ...>`
+///
+fn infer_decoder(ty: Option<&Type>) -> Inferred {
+    match ty {
+        Some(Type::Tuple(tuple)) if tuple.elems.is_empty() => Inferred::Ignore,
+        Some(Type::Path(path)) if path.qself.is_none() => match path.path.segments.last() {
+            Some(segment) if segment.arguments.is_none() => {
+                let decoder = if segment.ident == symbol::STRING {
+                    Some(symbol::TEXT)
+                } else if segment.ident == symbol::BYTES {
+                    Some(symbol::BYTES)
+                } else if segment.ident == symbol::RESPONSE {
+                    Some(symbol::RESPONSE)
+                } else {
+                    None
+                };
+                match decoder {
+                    Some(decoder) => {
+                        let decoder = format_ident!("{}", decoder);
+                        Inferred::Decoder(parse_quote!(hadorn::codec::#decoder))
+                    }
+                    None => Inferred::Default,
+                }
+            }
+            _ => Inferred::Default,
+        },
+        _ => Inferred::Default,
+    }
+}
+
+///
+/// the first generic type argument of the return type
+///
+fn get_ok_type(output: &ReturnType) -> Option<&Type> {
+    let ty = match output {
+        ReturnType::Type(_, ty) => ty.as_ref(),
+        ReturnType::Default => return None,
+    };
+    match ty {
+        Type::Path(path) => get_type_argument(&path.path.segments.last()?.arguments),
+        _ => None,
+    }
+}
+
+fn get_type_argument(arguments: &PathArguments) -> Option<&Type> {
+    match arguments {
+        PathArguments::AngleBracketed(arguments) => {
+            arguments.args.iter().find_map(|argument| match argument {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+///
+/// the `#[response_header = "..."]` attributes bind to the return tuple `(T, H1, H2...)` in order,
+/// return the body type `T` and the headers
+///
+fn get_response_headers<'a>(
+    attrs: &[Attribute],
+    output: &ReturnType,
+    ty: Option<&'a Type>,
+) -> syn::Result<(Option<&'a Type>, Vec<ResponseHeader>)> {
+    let names = attrs
+        .iter()
+        .filter(|attr| attr.style == AttrStyle::Outer)
+        .filter(|attr| attr.path() == symbol::RESPONSE_HEADER)
+        .map(|attr| {
+            let name_value = attr.meta.require_name_value()?;
+            match &name_value.value {
+                Expr::Lit(lit) => match &lit.lit {
+                    Lit::Str(name) if !name.value().is_empty() => Ok(name.clone()),
+                    _ => Err(Error::new_spanned(
+                        lit,
+                        "attribute `response_header` only supports non-empty literal strings",
+                    )),
+                },
+                value => Err(Error::new_spanned(
+                    value,
+                    "attribute `response_header` only supports non-empty literal strings",
+                )),
+            }
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    if names.is_empty() {
+        return Ok((ty, vec![]));
+    }
+    let elems = match ty {
+        Some(Type::Tuple(tuple)) if tuple.elems.len() == names.len() + 1 => &tuple.elems,
+        _ => {
+            return Err(Error::new_spanned(
+                output,
+                format!(
+                    "expected the return tuple `(T, ...)` with {} response header element(s)",
+                    names.len()
+                ),
+            ))
+        }
+    };
+    let headers = names
+        .into_iter()
+        .zip(elems.iter().skip(1))
+        .map(|(name, ty)| {
+            let inner = get_optional_type(ty);
+            ResponseHeader {
+                name,
+                ty: inner.unwrap_or(ty).clone(),
+                optional: inner.is_some(),
+            }
+        })
+        .collect();
+    Ok((elems.first(), headers))
+}
+
+///
+/// the inner type of the `Option<T>`
+///
+fn get_optional_type(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .filter(|segment| segment.ident == symbol::OPTION)
+            .and_then(|segment| get_type_argument(&segment.arguments)),
+        _ => None,
+    }
+}
+
+///
+/// the body type of the `hadorn::Response<T>`, only the full path is matched, the others such as `Response<T>`
+/// and `api::Response<T>` are the user types
+///
+fn get_wrapped_type(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    match path.segments.len() {
+        2 if path.segments[0].ident == symbol::HADORN
+            && path.segments[1].ident == symbol::RESPONSE =>
+        {
+            get_type_argument(&path.segments[1].arguments)
+        }
+        _ => None,
+    }
+}
+
+///
+/// the built-in decoders based on `serde`
+///
+fn is_serde_decoder(decoder: &Path) -> bool {
+    let segments = &decoder.segments;
+    segments.len() == 3
+        && segments[0].ident == symbol::HADORN
+        && segments[1].ident == symbol::CODEC
+        && symbol::SERDE_DECODERS
+            .iter()
+            .any(|name| segments[2].ident == *name)
+}
+
+///
+/// resolve the built-in decoder names to the `hadorn::codec` types, others are the user types
+///
+fn get_decoder(deserialized: Path) -> Path {
+    match deserialized.get_ident() {
+        Some(ident) if symbol::DECODERS.iter().any(|decoder| ident == decoder) => {
+            parse_quote!(hadorn::codec::#ident)
+        }
+        _ => deserialized,
+    }
+}
+
+fn get_deserialized(attrs: &[Attribute]) -> syn::Result<Option<Path>> {
     match get_name_value(attrs, symbol::DESERIALIZED)
         .cloned()
         .map(|name_value| name_value.value)
     {
         None => Ok(None),
-        Some(expr) => get_expr_ident(&expr)
-            .ok_or_else(|| Error::new(Span::call_site(), "invalid attribute: `deserialized`"))
+        Some(expr) => get_expr_path(&expr)
+            .ok_or_else(|| Error::new_spanned(&expr, "invalid attribute: `deserialized`"))
+            .map(Some),
+    }
+}
+
+fn get_error_body(attrs: &[Attribute]) -> syn::Result<Option<Path>> {
+    match get_name_value(attrs, symbol::ERROR_BODY)
+        .cloned()
+        .map(|name_value| name_value.value)
+    {
+        None => Ok(None),
+        Some(expr) => get_expr_path(&expr)
+            .ok_or_else(|| Error::new_spanned(&expr, "invalid attribute: `error_body`"))
             .map(Some),
     }
 }
@@ -70,8 +413,29 @@ mod symbol {
     use crate::symbol::Symbol;
 
     pub const DESERIALIZED: Symbol = Symbol("deserialized");
-    pub const JSON: Symbol = Symbol("Json");
+    pub const ERROR_BODY: Symbol = Symbol("error_body");
+    pub const STRING: Symbol = Symbol("String");
     pub const TEXT: Symbol = Symbol("Text");
     pub const BYTES: Symbol = Symbol("Bytes");
     pub const RESPONSE: Symbol = Symbol("Response");
+    pub const HADORN: Symbol = Symbol("hadorn");
+    pub const CODEC: Symbol = Symbol("codec");
+    pub const RESPONSE_HEADER: Symbol = Symbol("response_header");
+    pub const OPTION: Symbol = Symbol("Option");
+    pub const DECODERS: [Symbol; 8] = [
+        Symbol("Json"),
+        Symbol("Text"),
+        Symbol("Bytes"),
+        Symbol("Response"),
+        Symbol("Xml"),
+        Symbol("MsgPack"),
+        Symbol("Cbor"),
+        Symbol("Protobuf"),
+    ];
+    pub const SERDE_DECODERS: [Symbol; 4] = [
+        Symbol("Json"),
+        Symbol("Xml"),
+        Symbol("MsgPack"),
+        Symbol("Cbor"),
+    ];
 }
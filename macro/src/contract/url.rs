@@ -1,44 +1,150 @@
-use crate::contract::path::PathParams;
-use crate::meta::{Kind, PatMetas};
+use crate::contract::path::{get_format_pattern, get_literal, params, Param, Part, PathTemplate};
+use crate::meta::{Kind, PatMeta, PatMetas};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
+use std::collections::HashSet;
+use syn::LitStr;
 
 pub struct UrlTokens<'a> {
     metas: &'a PatMetas,
-    pattern: String,
+    template: PathTemplate,
 }
 
 impl<'a> UrlTokens<'a> {
-    pub fn new(metas: &'a PatMetas, path: &'a str) -> syn::Result<Self> {
-        let _ = PathParams::new(path).check(metas)?;
-        let pattern = PathParams::get_format_pattern(path);
-        Ok(Self { pattern, metas })
+    pub fn new(metas: &'a PatMetas, path: &LitStr) -> syn::Result<Self> {
+        let template = PathTemplate::parse(path)?.check(metas)?;
+        Ok(Self { metas, template })
+    }
+
+    ///
+    /// the names of the params bound in the query part of the path template
+    ///
+    pub fn query_params(&self) -> HashSet<String> {
+        self.template
+            .query_params()
+            .map(|param| param.name.clone())
+            .collect()
+    }
+
+    fn find_meta(&self, name: &str, kinds: &[Kind]) -> Option<&PatMeta> {
+        kinds.iter().find_map(|kind| {
+            self.metas
+                .iter()
+                .filter(|meta| meta.kind == *kind)
+                .find(|meta| meta.name() == name)
+        })
+    }
+
+    fn pair_tokens(&self, pair: &[Part]) -> TokenStream {
+        let metas = params(pair)
+            .filter_map(|param| self.find_meta(&param.name, &[Kind::Path, Kind::Query]))
+            .collect::<Vec<_>>();
+        let args = metas.iter().map(|meta| {
+            let arg = match meta.absent() {
+                true => {
+                    let ident = &meta.ident;
+                    quote! { #ident }
+                }
+                false => meta.value(),
+            };
+            match meta.raw {
+                true => quote! { #arg },
+                false => quote! { hadorn::__private::QueryComponent(&#arg) },
+            }
+        });
+        let value = match metas.is_empty() {
+            true => {
+                let literal = get_literal(pair);
+                quote! { #literal.to_string() }
+            }
+            false => {
+                let pattern = get_format_pattern(pair);
+                quote! { format!(#pattern, #(#args),*) }
+            }
+        };
+        // the param can be used several times in a pair, bind the optional argument once
+        let optionals = metas
+            .iter()
+            .filter(|meta| meta.absent())
+            .map(|meta| &meta.ident)
+            .fold(Vec::new(), |mut optionals, ident| {
+                if !optionals.contains(&ident) {
+                    optionals.push(ident);
+                }
+                optionals
+            });
+        match optionals.is_empty() {
+            true => quote! { Some(#value) },
+            false => quote! {
+                match (#(&#optionals,)*) {
+                    (#(Some(#optionals),)*) => Some(#value),
+                    _ => None,
+                }
+            },
+        }
+    }
+
+    fn path_arg_tokens(meta: &PatMeta, param: &Param) -> TokenStream {
+        let arg = &meta.ident;
+        let name = &param.name;
+        match (meta.raw, param.catch_all) {
+            (true, _) => quote! { #arg },
+            (false, true) => quote! { hadorn::__private::path_tail(self.client(), #name, &#arg)? },
+            (false, false) => {
+                quote! { hadorn::__private::path_segment(self.client(), #name, &#arg)? }
+            }
+        }
     }
 }
 
 impl ToTokens for UrlTokens<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let metas = self
-            .metas
+        let pattern = self.template.get_format_pattern();
+        let literal = self.template.get_literal();
+        let args = self
+            .template
+            .path_params()
+            .filter_map(|param| {
+                self.find_meta(&param.name, &[Kind::Path])
+                    .map(|meta| Self::path_arg_tokens(meta, param))
+            })
+            .collect::<Vec<_>>();
+        let pairs = self
+            .template
+            .query_pairs()
             .iter()
-            .filter(|meta| meta.kind == Kind::Path)
+            .map(|pair| self.pair_tokens(pair))
             .collect::<Vec<_>>();
-        let pattern = &self.pattern;
-        let stream = match metas.is_empty() {
-            true => quote! {
+        let stream = match (args.is_empty(), pairs.is_empty()) {
+            (true, true) => quote! {
                  let __url = match self.base_url() {
-                    Some(base_url) => format!("{}{}", base_url, #pattern),
-                    None => #pattern.to_string(),
+                    Some(base_url) => format!("{}{}", base_url, #literal),
+                    None => #literal.to_string(),
                 };
             },
-            false => {
-                let args = metas
-                    .iter()
-                    .map(|meta| &meta.ident)
-                    .map(|arg| quote! { #arg });
+            (_, has_no_query) => {
+                let path = match args.is_empty() {
+                    true => quote! { #literal.to_string() },
+                    false => quote! { format!(#pattern, #(#args),*) },
+                };
+                let query = match has_no_query {
+                    true => None,
+                    false => Some(quote! {
+                        let query = [#(#pairs),*]
+                            .into_iter()
+                            .flatten()
+                            .collect::<Vec<String>>()
+                            .join("&");
+                        let path = match query.is_empty() {
+                            true => path,
+                            false => format!("{}?{}", path, query),
+                        };
+                    }),
+                };
                 quote! {
                     let __url = {
-                        let path = format!(#pattern, #(#args),*);
+                        let path = #path;
+                        #query
                         match self.base_url() {
                             Some(base_url) => format!("{}{}", base_url, path),
                             None => path,
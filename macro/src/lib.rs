@@ -7,8 +7,8 @@ mod util;
 use crate::resource::Resource;
 use contract::Contract;
 use proc_macro::TokenStream;
-use proc_macro2::{Ident, Span};
-use syn::{parse_macro_input, ItemTrait, TraitItemFn};
+use proc_macro2::Span;
+use syn::{parse_macro_input, Error, ItemTrait, LitStr, TraitItemFn};
 
 #[proc_macro_attribute]
 pub fn hadorn(args: TokenStream, stream: TokenStream) -> TokenStream {
@@ -16,7 +16,7 @@ pub fn hadorn(args: TokenStream, stream: TokenStream) -> TokenStream {
     let item = parse_macro_input!(stream as ItemTrait);
     resource
         .expand(item)
-        .expect("expand attribute `resource` failed`")
+        .unwrap_or_else(Error::into_compile_error)
         .into()
 }
 
@@ -39,6 +39,9 @@ macro_rules! http_method {
     (options) => {
         "OPTIONS"
     };
+    (patch) => {
+        "PATCH"
+    };
     (trace) => {
         "TRACE"
     };
@@ -52,11 +55,30 @@ macro_rules! impl_methods {
             pub fn $method(args: TokenStream, input: TokenStream) -> TokenStream {
                 let item = parse_macro_input!(input as TraitItemFn);
                 let mut contract = parse_macro_input!(args as Contract);
-                contract.method = Some(Ident::new(http_method!($method), Span::call_site()));
-                contract.expand(item).expect(concat!("expand attribute `", stringify!($method) ,"` failed")).into()
+                if let Some(method) = &contract.method {
+                    return Error::new_spanned(
+                        method,
+                        concat!("attribute `method` is not allowed in `", stringify!($method), "`, use `request` instead"),
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+                contract.method = Some(LitStr::new(http_method!($method), Span::call_site()));
+                contract.expand(item).unwrap_or_else(Error::into_compile_error).into()
             }
         )*
     };
 }
 
-impl_methods!(get, post, put, delete, head, options, trace);
+impl_methods!(get, post, put, delete, head, options, patch, trace);
+
+/// Auto generate http call for any http method, example: `#[request(method = "PROPFIND", path = "/")]`
+#[proc_macro_attribute]
+pub fn request(args: TokenStream, input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as TraitItemFn);
+    let contract = parse_macro_input!(args as Contract);
+    contract
+        .expand(item)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
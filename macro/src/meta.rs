@@ -1,4 +1,8 @@
 use crate::symbol::Symbol;
+use crate::util::{unknown_argument, ExprArg, StrArg};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::{
     AttrStyle, Attribute, Error, Expr, FnArg, Ident, Lit, LitStr, Meta, Pat, PatType, Token, Type,
@@ -47,18 +51,65 @@ pub struct PatMeta {
     pub kind: Kind,
     pub optional: bool,
     pub rename: Option<LitStr>,
+    pub raw: bool,
+    pub style: Option<QueryStyle>,
+    pub flatten: bool,
+    pub default: Option<Expr>,
     pub reference: bool,
 }
 
+impl PatMeta {
+    ///
+    /// the request param name, use the rename if present
+    ///
+    pub fn name(&self) -> String {
+        match &self.rename {
+            Some(rename) => rename.value(),
+            None => self.ident.to_string(),
+        }
+    }
+
+    ///
+    /// the argument can be absent: `#[optional]` without a default value
+    ///
+    pub fn absent(&self) -> bool {
+        self.optional && self.default.is_none()
+    }
+
+    ///
+    /// the argument value, use the default value if the `#[optional]` argument is `None`
+    ///
+    pub fn value(&self) -> TokenStream {
+        let ident = &self.ident;
+        match &self.default {
+            Some(default) => quote! { #ident.unwrap_or_else(|| #default) },
+            None => quote! { #ident },
+        }
+    }
+}
+
 impl TryFrom<&'_ PatType> for PatMeta {
     type Error = Error;
 
     fn try_from(pat: &PatType) -> Result<Self, Self::Error> {
         let PatType { attrs, pat, ty, .. } = pat;
         let ident = get_ident(pat)?;
-        let optional = get_optional(attrs);
         let kind = get_kind(&ident, attrs)?;
-        let rename = get_rename(&ident, attrs, &kind)?;
+        let KindArgs {
+            rename,
+            raw,
+            style,
+            flatten,
+            default,
+        } = get_kind_args(&ident, attrs, &kind)?;
+        // the argument with default value is always optional
+        let optional = get_optional(attrs) || default.is_some();
+        if optional && kind == Kind::Path {
+            return Err(Error::new_spanned(
+                &ident,
+                "attribute `optional` not supported by `#[path]`",
+            ));
+        }
         let reference = matches!(ty.as_ref(), Type::Reference(_));
         Ok(Self {
             ident,
@@ -66,6 +117,10 @@ impl TryFrom<&'_ PatType> for PatMeta {
             optional,
             kind,
             rename,
+            raw,
+            style,
+            flatten,
+            default,
             reference,
         })
     }
@@ -104,33 +159,124 @@ fn get_kind(ident: &Ident, attr: &[Attribute]) -> syn::Result<Kind> {
     }
 }
 
-fn get_rename(ident: &Ident, attrs: &[Attribute], kind: &Kind) -> syn::Result<Option<LitStr>> {
+fn get_kind_args(ident: &Ident, attrs: &[Attribute], kind: &Kind) -> syn::Result<KindArgs> {
     let attr = attrs
         .iter()
         .filter(|attr| attr.style == AttrStyle::Outer)
         .find(|attr| attr.path() == kind.symbol());
-    match attr {
+    let args = match attr {
         Some(attr) => match &attr.meta {
-            Meta::Path(_) => Ok(None),
-            Meta::List(_) => Err(Error::new_spanned(
-                ident,
-                "attribute `rename` unsupported meta list",
-            )),
+            Meta::Path(_) => KindArgs::default(),
+            Meta::List(list) => list.parse_args::<KindArgs>()?,
             Meta::NameValue(name_value) => match &name_value.value {
                 Expr::Lit(lit) => match &lit.lit {
-                    Lit::Str(lit) => Ok(Some(lit.clone())),
-                    _ => Err(Error::new_spanned(
-                        ident,
-                        "attribute `rename` only supports literal strings",
-                    )),
+                    Lit::Str(lit) => KindArgs {
+                        rename: Some(lit.clone()),
+                        ..KindArgs::default()
+                    },
+                    lit => {
+                        return Err(Error::new_spanned(
+                            lit,
+                            "attribute `rename` only supports literal strings",
+                        ))
+                    }
                 },
-                _ => Err(Error::new_spanned(
-                    ident,
-                    "attribute `rename` only supports literal strings",
-                )),
+                value => {
+                    return Err(Error::new_spanned(
+                        value,
+                        "attribute `rename` only supports literal strings",
+                    ))
+                }
             },
         },
-        None => Err(Error::new_spanned(ident, "missing kind attribute")),
+        None => return Err(Error::new_spanned(ident, "missing kind attribute")),
+    };
+    if args.raw && *kind != Kind::Path {
+        return Err(Error::new_spanned(
+            attr,
+            "attribute `raw` only supported by `#[path]`",
+        ));
+    }
+    if args.style.is_some() && *kind != Kind::Query {
+        return Err(Error::new_spanned(
+            attr,
+            "attribute `style` only supported by `#[query]`",
+        ));
+    }
+    if args.flatten && *kind != Kind::Query {
+        return Err(Error::new_spanned(
+            attr,
+            "attribute `flatten` only supported by `#[query]`",
+        ));
+    }
+    if args.default.is_some() && !matches!(kind, Kind::Query | Kind::Header) {
+        return Err(Error::new_spanned(
+            attr,
+            "attribute `default` only supported by `#[query]` and `#[header]`",
+        ));
+    }
+    if args.flatten && (args.rename.is_some() || args.style.is_some()) {
+        return Err(Error::new_spanned(
+            attr,
+            "attribute `flatten` can not be used with `rename` or `style`",
+        ));
+    }
+    Ok(args)
+}
+
+#[derive(Default)]
+struct KindArgs {
+    rename: Option<LitStr>,
+    raw: bool,
+    style: Option<QueryStyle>,
+    flatten: bool,
+    default: Option<Expr>,
+}
+
+impl Parse for KindArgs {
+    //noinspection DuplicatedCode
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = KindArgs::default();
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::rename) {
+                if args.rename.is_some() {
+                    return Err(input.error("duplicate attribute `rename`"));
+                }
+                let rename = input.parse::<StrArg<kw::rename>>()?;
+                args.rename = Some(rename.value);
+            } else if lookahead.peek(kw::raw) {
+                if args.raw {
+                    return Err(input.error("duplicate attribute `raw`"));
+                }
+                let _ = input.parse::<kw::raw>()?;
+                args.raw = true;
+            } else if lookahead.peek(kw::style) {
+                if args.style.is_some() {
+                    return Err(input.error("duplicate attribute `style`"));
+                }
+                let style = input.parse::<StrArg<kw::style>>()?;
+                args.style = Some(QueryStyle::parse(&style.value)?);
+            } else if lookahead.peek(kw::flatten) {
+                if args.flatten {
+                    return Err(input.error("duplicate attribute `flatten`"));
+                }
+                let _ = input.parse::<kw::flatten>()?;
+                args.flatten = true;
+            } else if lookahead.peek(kw::default) {
+                if args.default.is_some() {
+                    return Err(input.error("duplicate attribute `default`"));
+                }
+                let default = input.parse::<ExprArg<kw::default>>()?;
+                args.default = Some(default.value);
+            } else {
+                return Err(unknown_argument(input, kw::KEYWORDS));
+            }
+            if !input.is_empty() {
+                let _ = input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
     }
 }
 
@@ -153,6 +299,60 @@ impl Kind {
     }
 }
 
+///
+/// the collection style of the `#[query]` argument
+///
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum QueryStyle {
+    /// repeated keys: `ids=1&ids=2`
+    Form,
+    /// comma-separated values: `ids=1,2`
+    Csv,
+    /// bracketed keys: `ids[]=1&ids[]=2`
+    Brackets,
+    /// pipe-delimited values: `ids=1|2`
+    Pipes,
+}
+
+impl QueryStyle {
+    pub fn parse(style: &LitStr) -> syn::Result<Self> {
+        match style.value().as_str() {
+            "form" => Ok(QueryStyle::Form),
+            "csv" => Ok(QueryStyle::Csv),
+            "brackets" => Ok(QueryStyle::Brackets),
+            "pipes" => Ok(QueryStyle::Pipes),
+            _ => Err(Error::new_spanned(
+                style,
+                "invalid query style, expected one of: `form`, `csv`, `brackets`, `pipes`",
+            )),
+        }
+    }
+
+    pub fn ident(&self) -> Ident {
+        let name = match self {
+            QueryStyle::Form => "Form",
+            QueryStyle::Csv => "Csv",
+            QueryStyle::Brackets => "Brackets",
+            QueryStyle::Pipes => "Pipes",
+        };
+        Ident::new(name, Span::call_site())
+    }
+}
+
+///
+/// check the type is a collection: `Vec<T>`、`[T]`、`[T; N]`、`HashSet<T>`... or the reference of them
+///
+pub fn is_collection(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => is_collection(&reference.elem),
+        Type::Slice(_) | Type::Array(_) => true,
+        Type::Path(path) => path.path.segments.last().is_some_and(|segment| {
+            ["Vec", "VecDeque", "HashSet", "BTreeSet"].contains(&segment.ident.to_string().as_str())
+        }),
+        _ => false,
+    }
+}
+
 mod symbol {
     use crate::symbol::Symbol;
 
@@ -162,3 +362,15 @@ mod symbol {
     pub const HEADER: Symbol = Symbol("header");
     pub const OPTIONAL: Symbol = Symbol("optional");
 }
+
+mod kw {
+    use syn::custom_keyword;
+
+    custom_keyword!(rename);
+    custom_keyword!(raw);
+    custom_keyword!(style);
+    custom_keyword!(flatten);
+    custom_keyword!(default);
+
+    pub const KEYWORDS: &[&str] = &["rename", "raw", "style", "flatten", "default"];
+}
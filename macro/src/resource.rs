@@ -1,14 +1,22 @@
-use crate::util::{http_mod_path, reqwest_mod_path, ExprArg};
-use proc_macro2::{Ident, TokenStream, TokenTree};
+use crate::contract::{is_nested, parse_timeout};
+use crate::meta::QueryStyle;
+use crate::util::{http_mod_path, reqwest_mod_path, unknown_argument, ExprArg, StrArg};
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 use syn::parse::{Parse, ParseStream};
-use syn::{parse_quote, ItemTrait, Token, TraitItem, TypeParamBound};
+use syn::{parse_quote, ItemTrait, LitStr, Path, Token, TraitItem, TypeParamBound};
 
 #[derive(Debug, Default)]
 pub struct Resource {
     client: Option<Ident>,
-    serialized: Option<Ident>,
-    deserialized: Option<Ident>,
+    serialized: Option<Path>,
+    deserialized: Option<Path>,
+    query_style: Option<LitStr>,
+    query_encoding: Option<Ident>,
+    error: Option<Path>,
+    error_body: Option<Path>,
+    problem_details: bool,
+    timeout: Option<LitStr>,
 }
 
 impl Parse for Resource {
@@ -31,7 +39,7 @@ impl Parse for Resource {
                 }
                 let serialize = input
                     .parse::<ExprArg<kw::serialized>>()
-                    .and_then(|serialize| serialize.require_ident())?;
+                    .and_then(|serialize| serialize.require_path())?;
                 resource.serialized = Some(serialize);
             } else if lookahead.peek(kw::deserialized) {
                 if resource.deserialized.is_some() {
@@ -39,12 +47,58 @@ impl Parse for Resource {
                 }
                 let deserialize = input
                     .parse::<ExprArg<kw::deserialized>>()
-                    .and_then(|deserialize| deserialize.require_ident())?;
+                    .and_then(|deserialize| deserialize.require_path())?;
                 resource.deserialized = Some(deserialize);
-            } else if lookahead.peek(Token![,]) {
-                let _ = input.parse::<Token![,]>()?;
+            } else if lookahead.peek(kw::query_style) {
+                if resource.query_style.is_some() {
+                    return Err(input.error("duplicate attribute `query_style`"));
+                }
+                let query_style = input.parse::<StrArg<kw::query_style>>()?;
+                let _ = QueryStyle::parse(&query_style.value)?;
+                resource.query_style = Some(query_style.value);
+            } else if lookahead.peek(kw::query_encoding) {
+                if resource.query_encoding.is_some() {
+                    return Err(input.error("duplicate attribute `query_encoding`"));
+                }
+                let query_encoding = input
+                    .parse::<ExprArg<kw::query_encoding>>()
+                    .and_then(|query_encoding| query_encoding.require_ident())?;
+                let _ = is_nested(&query_encoding)?;
+                resource.query_encoding = Some(query_encoding);
+            } else if lookahead.peek(kw::error) {
+                if resource.error.is_some() {
+                    return Err(input.error("duplicate attribute `error`"));
+                }
+                let error = input
+                    .parse::<ExprArg<kw::error>>()
+                    .and_then(|error| error.require_path())?;
+                resource.error = Some(error);
+            } else if lookahead.peek(kw::error_body) {
+                if resource.error_body.is_some() {
+                    return Err(input.error("duplicate attribute `error_body`"));
+                }
+                let error_body = input
+                    .parse::<ExprArg<kw::error_body>>()
+                    .and_then(|error_body| error_body.require_path())?;
+                resource.error_body = Some(error_body);
+            } else if lookahead.peek(kw::problem_details) {
+                if resource.problem_details {
+                    return Err(input.error("duplicate attribute `problem_details`"));
+                }
+                let _ = input.parse::<kw::problem_details>()?;
+                resource.problem_details = true;
+            } else if lookahead.peek(kw::timeout) {
+                if resource.timeout.is_some() {
+                    return Err(input.error("duplicate attribute `timeout`"));
+                }
+                let timeout = input.parse::<StrArg<kw::timeout>>()?;
+                let _ = parse_timeout(&timeout.value)?;
+                resource.timeout = Some(timeout.value);
             } else {
-                let _ = input.parse::<TokenTree>()?;
+                return Err(unknown_argument(input, kw::KEYWORDS));
+            }
+            if !input.is_empty() {
+                let _ = input.parse::<Token![,]>()?;
             }
         }
         Ok(resource)
@@ -58,15 +112,31 @@ impl Resource {
         let client = self.client.unwrap_or(format_ident!("{}Client", name));
         let serialized = self.serialized;
         let deserialized = self.deserialized;
+        let query_style = self.query_style;
+        let query_encoding = self.query_encoding;
+        let error = self.error;
+        let error_body = self.error_body;
+        let problem_details = self.problem_details;
+        let timeout = self.timeout;
 
         // insert [Hadorn] super trait
         item_trait
             .supertraits
             .push(TypeParamBound::Trait(parse_quote! { hadorn::Hadorn }));
 
-        // add serialized and deserialized attribute
+        // add resource, serialized, deserialized, query_style, query_encoding, error, error_body, problem_details and timeout attribute
+        let resource = LitStr::new(&name.to_string(), name.span());
         item_trait.items.iter_mut().for_each(|item| {
             if let TraitItem::Fn(item_fn) = item {
+                // move the inert `response_header` attributes after the http method attribute,
+                // they are consumed by it
+                let (headers, attrs) = item_fn
+                    .attrs
+                    .drain(..)
+                    .partition::<Vec<_>, _>(|attr| attr.path() == symbol::RESPONSE_HEADER);
+                item_fn.attrs = attrs;
+                item_fn.attrs.extend(headers);
+                item_fn.attrs.push(parse_quote!(#[resource = #resource]));
                 if let Some(serialized) = &serialized {
                     item_fn
                         .attrs
@@ -77,6 +147,30 @@ impl Resource {
                         .attrs
                         .push(parse_quote!(#[deserialized = #deserialized]));
                 }
+                if let Some(query_style) = &query_style {
+                    item_fn
+                        .attrs
+                        .push(parse_quote!(#[query_style = #query_style]));
+                }
+                if let Some(query_encoding) = &query_encoding {
+                    item_fn
+                        .attrs
+                        .push(parse_quote!(#[query_encoding = #query_encoding]));
+                }
+                if let Some(error) = &error {
+                    item_fn.attrs.push(parse_quote!(#[error = #error]));
+                }
+                if let Some(error_body) = &error_body {
+                    item_fn
+                        .attrs
+                        .push(parse_quote!(#[error_body = #error_body]));
+                }
+                if problem_details {
+                    item_fn.attrs.push(parse_quote!(#[problem_details]));
+                }
+                if let Some(timeout) = &timeout {
+                    item_fn.attrs.push(parse_quote!(#[timeout = #timeout]));
+                }
             }
         });
 
@@ -158,10 +252,34 @@ impl Resource {
     }
 }
 
+mod symbol {
+    use crate::symbol::Symbol;
+
+    pub const RESPONSE_HEADER: Symbol = Symbol("response_header");
+}
+
 mod kw {
     use syn::custom_keyword;
 
     custom_keyword!(client);
     custom_keyword!(serialized);
     custom_keyword!(deserialized);
+    custom_keyword!(query_style);
+    custom_keyword!(query_encoding);
+    custom_keyword!(error);
+    custom_keyword!(error_body);
+    custom_keyword!(problem_details);
+    custom_keyword!(timeout);
+
+    pub const KEYWORDS: &[&str] = &[
+        "client",
+        "serialized",
+        "deserialized",
+        "query_style",
+        "query_encoding",
+        "error",
+        "error_body",
+        "problem_details",
+        "timeout",
+    ];
 }
@@ -2,6 +2,7 @@ use crate::symbol::Symbol;
 use proc_macro2::Ident;
 use quote::format_ident;
 use std::marker::PhantomData;
+use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::{
     parenthesized, parse_quote, AttrStyle, Attribute, Error, Expr, Lit, LitStr, MetaNameValue,
@@ -38,6 +39,12 @@ impl<T> ExprArg<T> {
             Error::new_spanned(&self.value, "only identifier or string literal allowed")
         })
     }
+
+    pub fn require_path(&self) -> syn::Result<Path> {
+        get_expr_path(&self.value).ok_or_else(|| {
+            Error::new_spanned(&self.value, "only path or string literal allowed")
+        })
+    }
 }
 
 pub struct StrTuple(pub LitStr, pub LitStr);
@@ -70,6 +77,20 @@ pub fn get_expr_ident(expr: &Expr) -> Option<Ident> {
     }
 }
 
+pub fn get_expr_path(expr: &Expr) -> Option<Path> {
+    match expr {
+        Expr::Lit(lit) => {
+            if let Lit::Str(lit) = &lit.lit {
+                lit.parse().ok()
+            } else {
+                None
+            }
+        }
+        Expr::Path(path) => Some(path.path.clone()),
+        _ => None,
+    }
+}
+
 pub fn http_mod_path() -> Path {
     parse_quote!(hadorn::__http)
 }
@@ -86,3 +107,112 @@ pub fn get_name_value(attrs: &[Attribute], name: Symbol) -> Option<&MetaNameValu
         .flat_map(|attr| attr.meta.require_name_value())
         .next()
 }
+
+pub fn unknown_argument(input: ParseStream, keywords: &[&str]) -> Error {
+    let expected = keywords
+        .iter()
+        .map(|keyword| format!("`{}`", keyword))
+        .collect::<Vec<_>>()
+        .join(", ");
+    match input.fork().call(Ident::parse_any) {
+        Ok(ident) => {
+            let name = ident.to_string();
+            let message = match closest_keyword(&name, keywords) {
+                Some(keyword) => format!(
+                    "unknown attribute `{}`, did you mean `{}`?",
+                    name, keyword
+                ),
+                None => format!("unknown attribute `{}`, expected one of: {}", name, expected),
+            };
+            Error::new_spanned(ident, message)
+        }
+        Err(_) => input.error(format!("expected one of: {}", expected)),
+    }
+}
+
+fn closest_keyword<'a>(name: &str, keywords: &[&'a str]) -> Option<&'a str> {
+    keywords
+        .iter()
+        .map(|keyword| (*keyword, edit_distance(name, keyword)))
+        .filter(|(keyword, distance)| *distance <= keyword.len().max(name.len()) / 3 + 1)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(keyword, _)| keyword)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = match ca == *cb {
+                true => prev,
+                false => 1 + prev.min(row[j]).min(row[j + 1]),
+            };
+            prev = current;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{closest_keyword, edit_distance, unknown_argument};
+    use proc_macro2::TokenStream;
+    use syn::parse::{ParseStream, Parser};
+
+    const KEYWORDS: &[&str] = &["method", "path", "headers", "serialized", "deserialized"];
+
+    fn unknown(input: &str) -> String {
+        let parser = |input: ParseStream| {
+            let error = unknown_argument(input, KEYWORDS);
+            input.parse::<TokenStream>()?;
+            Ok(error)
+        };
+        parser.parse_str(input).unwrap().to_string()
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("path", "path"), 0);
+        assert_eq!(edit_distance("", "path"), 4);
+        assert_eq!(edit_distance("pth", "path"), 1);
+        assert_eq!(edit_distance("paht", "path"), 2);
+        assert_eq!(edit_distance("deserialize", "deserialized"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("路径", "路"), 1);
+    }
+
+    #[test]
+    fn closest() {
+        assert_eq!(closest_keyword("pth", KEYWORDS), Some("path"));
+        assert_eq!(closest_keyword("methd", KEYWORDS), Some("method"));
+        assert_eq!(closest_keyword("header", KEYWORDS), Some("headers"));
+        assert_eq!(
+            closest_keyword("deserialize", KEYWORDS),
+            Some("deserialized")
+        );
+        assert_eq!(closest_keyword("serialize", KEYWORDS), Some("serialized"));
+        assert_eq!(closest_keyword("timeout", KEYWORDS), None);
+        assert_eq!(closest_keyword("x", KEYWORDS), None);
+    }
+
+    #[test]
+    fn unknown_argument_message() {
+        assert_eq!(
+            unknown("pth = \"/users\""),
+            "unknown attribute `pth`, did you mean `path`?"
+        );
+        assert_eq!(
+            unknown("query = 1"),
+            "unknown attribute `query`, expected one of: `method`, `path`, `headers`, `serialized`, `deserialized`"
+        );
+        assert_eq!(
+            unknown("= 1"),
+            "expected one of: `method`, `path`, `headers`, `serialized`, `deserialized`"
+        );
+    }
+}
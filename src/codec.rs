@@ -0,0 +1,116 @@
+//!
+//! the built-in encoders and decoders
+//!
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+#[cfg(feature = "protobuf")]
+mod protobuf;
+#[cfg(feature = "xml")]
+mod xml;
+
+#[cfg(feature = "cbor")]
+pub use cbor::*;
+#[cfg(feature = "msgpack")]
+pub use msgpack::*;
+#[cfg(feature = "protobuf")]
+pub use protobuf::*;
+#[cfg(feature = "xml")]
+pub use xml::*;
+
+use crate::{Decoder, Encoder};
+use reqwest::RequestBuilder;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+///
+/// `application/json` body
+///
+pub struct Json;
+
+impl<T: Serialize> Encoder<T> for Json {
+    type Error = reqwest::Error;
+
+    fn encode(request: RequestBuilder, body: T) -> Result<RequestBuilder, Self::Error> {
+        Ok(request.json(&body))
+    }
+}
+
+impl<T: DeserializeOwned> Decoder<T> for Json {
+    type Error = reqwest::Error;
+
+    async fn decode(response: reqwest::Response) -> Result<T, Self::Error> {
+        response.json().await
+    }
+}
+
+///
+/// the response body as text
+///
+pub struct Text;
+
+impl Decoder<String> for Text {
+    type Error = reqwest::Error;
+
+    async fn decode(response: reqwest::Response) -> Result<String, Self::Error> {
+        response.text().await
+    }
+}
+
+///
+/// the response body as bytes
+///
+pub struct Bytes;
+
+impl Decoder<bytes::Bytes> for Bytes {
+    type Error = reqwest::Error;
+
+    async fn decode(response: reqwest::Response) -> Result<bytes::Bytes, Self::Error> {
+        response.bytes().await
+    }
+}
+
+///
+/// the raw response, the body is not read
+///
+pub struct Response;
+
+impl Decoder<reqwest::Response> for Response {
+    type Error = reqwest::Error;
+
+    async fn decode(response: reqwest::Response) -> Result<reqwest::Response, Self::Error> {
+        Ok(response)
+    }
+}
+
+///
+/// `application/x-www-form-urlencoded` body
+///
+pub struct Form;
+
+impl<T: Serialize> Encoder<T> for Form {
+    type Error = reqwest::Error;
+
+    fn encode(request: RequestBuilder, body: T) -> Result<RequestBuilder, Self::Error> {
+        Ok(request.form(&body))
+    }
+}
+
+///
+/// `multipart/form-data` body, requires the `multipart` feature
+///
+#[cfg(feature = "multipart")]
+pub struct Multipart;
+
+#[cfg(feature = "multipart")]
+impl Encoder<reqwest::multipart::Form> for Multipart {
+    type Error = reqwest::Error;
+
+    fn encode(
+        request: RequestBuilder,
+        body: reqwest::multipart::Form,
+    ) -> Result<RequestBuilder, Self::Error> {
+        Ok(request.multipart(body))
+    }
+}
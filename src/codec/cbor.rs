@@ -0,0 +1,36 @@
+use crate::private::{decode_error, encode_error};
+use crate::{Decoder, Encoder};
+use http::header::CONTENT_TYPE;
+use reqwest::{RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const APPLICATION_CBOR: &str = "application/cbor";
+
+///
+/// `application/cbor` body, requires the `cbor` feature
+///
+pub struct Cbor;
+
+impl<T: Serialize> Encoder<T> for Cbor {
+    type Error = reqwest::Error;
+
+    fn encode(request: RequestBuilder, body: T) -> Result<RequestBuilder, Self::Error> {
+        let mut buf = vec![];
+        match ciborium::into_writer(&body, &mut buf) {
+            Ok(()) => Ok(request.header(CONTENT_TYPE, APPLICATION_CBOR).body(buf)),
+            Err(e) => Ok(encode_error(request, e)),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Decoder<T> for Cbor {
+    type Error = reqwest::Error;
+
+    const ACCEPT: Option<&'static str> = Some(APPLICATION_CBOR);
+
+    async fn decode(response: Response) -> Result<T, Self::Error> {
+        let bytes = response.bytes().await?;
+        ciborium::from_reader(bytes.as_ref()).map_err(decode_error)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::private::{decode_error, encode_error};
+use crate::{Decoder, Encoder};
+use http::header::CONTENT_TYPE;
+use reqwest::{RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const APPLICATION_MSGPACK: &str = "application/msgpack";
+
+///
+/// `application/msgpack` body, requires the `msgpack` feature
+///
+pub struct MsgPack;
+
+impl<T: Serialize> Encoder<T> for MsgPack {
+    type Error = reqwest::Error;
+
+    fn encode(request: RequestBuilder, body: T) -> Result<RequestBuilder, Self::Error> {
+        match rmp_serde::to_vec_named(&body) {
+            Ok(body) => Ok(request.header(CONTENT_TYPE, APPLICATION_MSGPACK).body(body)),
+            Err(e) => Ok(encode_error(request, e)),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Decoder<T> for MsgPack {
+    type Error = reqwest::Error;
+
+    const ACCEPT: Option<&'static str> = Some(APPLICATION_MSGPACK);
+
+    async fn decode(response: Response) -> Result<T, Self::Error> {
+        let bytes = response.bytes().await?;
+        rmp_serde::from_slice(&bytes).map_err(decode_error)
+    }
+}
@@ -0,0 +1,36 @@
+use crate::private::decode_error;
+use crate::{Decoder, Encoder};
+use http::header::CONTENT_TYPE;
+use prost::Message;
+use reqwest::{RequestBuilder, Response};
+
+const APPLICATION_PROTOBUF: &str = "application/x-protobuf";
+
+///
+/// `application/x-protobuf` body of the `prost::Message`, requires the `protobuf` feature
+///
+/// the body is encoded from a reference: `#[body] event: &Event`, an owned encoder can not be implemented
+/// together as `prost` may implement `Message` for `&T`
+///
+pub struct Protobuf;
+
+impl<T: Message> Encoder<&T> for Protobuf {
+    type Error = reqwest::Error;
+
+    fn encode(request: RequestBuilder, body: &T) -> Result<RequestBuilder, Self::Error> {
+        Ok(request
+            .header(CONTENT_TYPE, APPLICATION_PROTOBUF)
+            .body(body.encode_to_vec()))
+    }
+}
+
+impl<T: Message + Default> Decoder<T> for Protobuf {
+    type Error = reqwest::Error;
+
+    const ACCEPT: Option<&'static str> = Some(APPLICATION_PROTOBUF);
+
+    async fn decode(response: Response) -> Result<T, Self::Error> {
+        let bytes = response.bytes().await?;
+        T::decode(bytes).map_err(decode_error)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::private::{decode_error, encode_error};
+use crate::{Decoder, Encoder};
+use http::header::CONTENT_TYPE;
+use reqwest::{RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const APPLICATION_XML: &str = "application/xml";
+
+///
+/// `application/xml` body, requires the `xml` feature
+///
+pub struct Xml;
+
+impl<T: Serialize> Encoder<T> for Xml {
+    type Error = reqwest::Error;
+
+    fn encode(request: RequestBuilder, body: T) -> Result<RequestBuilder, Self::Error> {
+        match quick_xml::se::to_string(&body) {
+            Ok(body) => Ok(request.header(CONTENT_TYPE, APPLICATION_XML).body(body)),
+            Err(e) => Ok(encode_error(request, e)),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Decoder<T> for Xml {
+    type Error = reqwest::Error;
+
+    const ACCEPT: Option<&'static str> = Some(APPLICATION_XML);
+
+    async fn decode(response: Response) -> Result<T, Self::Error> {
+        let text = response.text().await?;
+        quick_xml::de::from_str(&text).map_err(decode_error)
+    }
+}
@@ -0,0 +1,411 @@
+use crate::private::{builder_error, decode_error};
+use crate::ProblemDetails;
+use bytes::Bytes;
+use http::{HeaderMap, Method, StatusCode};
+use reqwest::Url;
+use std::any::Any;
+use std::fmt::{Debug, Display, Formatter};
+
+///
+/// the result of the generated methods
+///
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+///
+/// the error of the generated methods
+///
+#[derive(Debug)]
+pub enum Error {
+    ///
+    /// build or send the request failed, or read the response failed: connect, timeout...
+    ///
+    Transport(reqwest::Error),
+
+    ///
+    /// the response status is not success
+    ///
+    Status(Box<StatusError>),
+
+    ///
+    /// encode the request path or the request body failed
+    ///
+    Encode(Box<dyn std::error::Error + Send + Sync>),
+
+    ///
+    /// decode the response body or the response headers failed
+    ///
+    Decode(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Error {
+    ///
+    /// the error is a transport error
+    ///
+    pub fn is_transport(&self) -> bool {
+        matches!(self, Error::Transport(_))
+    }
+
+    ///
+    /// the error is a status error
+    ///
+    pub fn is_status(&self) -> bool {
+        matches!(self, Error::Status(_))
+    }
+
+    ///
+    /// the error is an encode error
+    ///
+    pub fn is_encode(&self) -> bool {
+        matches!(self, Error::Encode(_))
+    }
+
+    ///
+    /// the error is a decode error
+    ///
+    pub fn is_decode(&self) -> bool {
+        matches!(self, Error::Decode(_))
+    }
+
+    ///
+    /// the response status of the status error
+    ///
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Error::Status(e) => Some(e.status()),
+            Error::Transport(e) => e.status(),
+            _ => None,
+        }
+    }
+
+    ///
+    /// the problem details of the status error, see [`StatusError::problem_details`]
+    ///
+    pub fn problem_details(&self) -> Option<&ProblemDetails> {
+        match self {
+            Error::Status(e) => e.problem_details(),
+            _ => None,
+        }
+    }
+
+    ///
+    /// the decoded error body of the status error, see [`StatusError::error_body`]
+    ///
+    pub fn error_body<T: 'static>(&self) -> Option<&T> {
+        match self {
+            Error::Status(e) => e.error_body(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Transport(e) => write!(f, "{}", e),
+            Error::Status(e) => write!(f, "{}", e),
+            Error::Encode(e) => write!(f, "error encoding request body: {}", e),
+            Error::Decode(e) => write!(f, "error decoding response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(e) => Some(e),
+            Error::Status(e) => Some(e.as_ref()),
+            Error::Encode(e) => Some(e.as_ref()),
+            Error::Decode(e) => Some(e.as_ref()),
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        match e.is_decode() {
+            true => Error::Decode(Box::new(e)),
+            false => Error::Transport(e),
+        }
+    }
+}
+
+impl From<StatusError> for Error {
+    fn from(e: StatusError) -> Self {
+        Error::Status(Box::new(e))
+    }
+}
+
+impl From<HeaderError> for Error {
+    fn from(e: HeaderError) -> Self {
+        Error::Decode(Box::new(e))
+    }
+}
+
+impl From<PathError> for Error {
+    fn from(e: PathError) -> Self {
+        Error::Encode(Box::new(e))
+    }
+}
+
+///
+/// the decoded error body or the decode error
+///
+pub(crate) type ErrorBody =
+    std::result::Result<Box<dyn Any + Send + Sync>, Box<dyn std::error::Error + Send + Sync>>;
+
+///
+/// the response status is not success, keep the response status, headers and a truncated body snippet
+///
+pub struct StatusError {
+    pub(crate) status: StatusCode,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: String,
+    pub(crate) bytes: Bytes,
+    pub(crate) error_body: Option<ErrorBody>,
+    pub(crate) problem_details: Option<ProblemDetails>,
+    pub(crate) method: Method,
+    pub(crate) url: Url,
+    pub(crate) contract: &'static str,
+    pub(crate) source: reqwest::Error,
+}
+
+impl StatusError {
+    ///
+    /// the response status
+    ///
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    ///
+    /// the response headers
+    ///
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    ///
+    /// the response body snippet, truncated if the body is too long
+    ///
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    ///
+    /// the full raw response body
+    ///
+    pub fn bytes(&self) -> &Bytes {
+        &self.bytes
+    }
+
+    ///
+    /// the error body decoded by `error_body = MyErrorBody`, `None` if not configured, decode failed or the type not matched
+    ///
+    pub fn error_body<T: 'static>(&self) -> Option<&T> {
+        match &self.error_body {
+            Some(Ok(error_body)) => error_body.downcast_ref(),
+            _ => None,
+        }
+    }
+
+    ///
+    /// the problem details parsed from the `application/problem+json` response, requires `problem_details` enabled
+    ///
+    pub fn problem_details(&self) -> Option<&ProblemDetails> {
+        self.problem_details.as_ref()
+    }
+
+    ///
+    /// the error of decoding the error body, the raw body is still reachable by [`StatusError::bytes`]
+    ///
+    pub fn error_body_error(&self) -> Option<&(dyn std::error::Error + Send + Sync + 'static)> {
+        match &self.error_body {
+            Some(Err(e)) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+
+    ///
+    /// the request method
+    ///
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    ///
+    /// the request url
+    ///
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    ///
+    /// the contract method name: `Users::create`
+    ///
+    pub fn contract(&self) -> &'static str {
+        self.contract
+    }
+}
+
+impl Debug for StatusError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatusError")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .field("body", &self.body)
+            .field("problem_details", &self.problem_details)
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field("contract", &self.contract)
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Display for StatusError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` {} {} responded {}",
+            self.contract, self.method, self.url, self.status
+        )?;
+        if !self.body.is_empty() {
+            write!(f, ": {}", self.body)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for StatusError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+///
+/// keep the generated methods returning `reqwest::Result` compatible
+///
+impl From<StatusError> for reqwest::Error {
+    fn from(e: StatusError) -> Self {
+        e.source
+    }
+}
+
+///
+/// the response header bound by `#[response_header = "..."]` is missing or malformed
+///
+#[derive(Debug)]
+pub struct HeaderError {
+    name: &'static str,
+    source: Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+impl HeaderError {
+    pub(crate) fn missing(name: &'static str) -> Self {
+        Self { name, source: None }
+    }
+
+    pub(crate) fn invalid(
+        name: &'static str,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self {
+            name,
+            source: Some(source.into()),
+        }
+    }
+
+    ///
+    /// the header name
+    ///
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    ///
+    /// the header is missing, otherwise the header value is malformed
+    ///
+    pub fn is_missing(&self) -> bool {
+        self.source.is_none()
+    }
+}
+
+impl Display for HeaderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.source {
+            Some(source) => write!(f, "invalid response header `{}`: {}", self.name, source),
+            None => write!(f, "missing response header `{}`", self.name),
+        }
+    }
+}
+
+impl std::error::Error for HeaderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_ref().map(|source| source.as_ref() as _)
+    }
+}
+
+impl From<HeaderError> for reqwest::Error {
+    fn from(e: HeaderError) -> Self {
+        decode_error(e)
+    }
+}
+
+///
+/// the `#[path]` argument is a `.` or `..` segment, the url parser would resolve it and change the request path
+///
+#[derive(Debug)]
+pub struct PathError {
+    name: &'static str,
+    value: String,
+    source: reqwest::Error,
+}
+
+impl PathError {
+    pub(crate) fn new(client: &reqwest::Client, name: &'static str, value: String) -> Self {
+        let source = builder_error(
+            client,
+            format!("the path argument `{}` is a dot segment: `{}`", name, value),
+        );
+        Self {
+            name,
+            value,
+            source,
+        }
+    }
+
+    ///
+    /// the path variable name
+    ///
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    ///
+    /// the rejected path argument value
+    ///
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the path argument `{}` is a dot segment: `{}`",
+            self.name, self.value
+        )
+    }
+}
+
+impl std::error::Error for PathError {}
+
+///
+/// keep the generated methods returning `reqwest::Result` compatible
+///
+impl From<PathError> for reqwest::Error {
+    fn from(e: PathError) -> Self {
+        e.source
+    }
+}
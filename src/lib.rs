@@ -16,6 +16,76 @@ pub trait Hadorn {
     fn default_headers(&self) -> Option<&http::HeaderMap>;
 }
 
+pub mod codec;
+mod error;
+mod private;
+mod problem;
+mod response;
+
+pub use error::{Error, HeaderError, PathError, Result, StatusError};
+pub use problem::ProblemDetails;
+pub use response::Response;
+
+///
+/// encode the request body, set the body bytes and the `Content-Type` header on the request
+///
+/// use it with `serialized = MyEncoder`, the built-in encoders are in the [`codec`] module.
+///
+pub trait Encoder<T> {
+    ///
+    /// the encode error, the method result error must can be converted from it
+    ///
+    type Error;
+
+    ///
+    /// encode the body into the request
+    ///
+    fn encode(
+        request: reqwest::RequestBuilder,
+        body: T,
+    ) -> Result<reqwest::RequestBuilder, Self::Error>;
+}
+
+///
+/// decode the response body into the method result type
+///
+/// use it with `deserialized = MyDecoder`, the built-in decoders are in the [`codec`] module.
+///
+pub trait Decoder<T> {
+    ///
+    /// the decode error, the method result error must can be converted from it
+    ///
+    type Error;
+
+    ///
+    /// the `Accept` header of the request, not set if `None`
+    ///
+    const ACCEPT: Option<&'static str> = None;
+
+    ///
+    /// decode the response
+    ///
+    fn decode(
+        response: reqwest::Response,
+    ) -> impl std::future::Future<Output = Result<T, Self::Error>>;
+}
+
+///
+/// convert the [`Error`] into the custom error, use it with `error = MyError`
+///
+pub trait FromError {
+    ///
+    /// convert the error
+    ///
+    fn from_error(error: Error) -> Self;
+}
+
+impl FromError for Error {
+    fn from_error(error: Error) -> Self {
+        error
+    }
+}
+
 // export hadorn macro
 pub use hadorn_macro::*;
 
@@ -28,3 +98,47 @@ pub mod __http {
 pub mod __reqwest {
     pub use reqwest::*;
 }
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::private::*;
+}
+
+// report the disabled feature at the macro call site, instead of an unresolved path
+#[cfg(feature = "multipart")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __multipart_body {
+    ($request:ident, $body:expr) => {
+        <$crate::codec::Multipart as $crate::Encoder<_>>::encode($request, $body)?
+    };
+}
+
+#[cfg(not(feature = "multipart"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __multipart_body {
+    ($request:ident, $body:expr) => {
+        compile_error!(
+            "`serialized = Multipart` requires the `multipart` feature: `hadorn = { version = \"...\", features = [\"multipart\"] }`"
+        );
+    };
+}
+
+#[cfg(feature = "qs")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __require_qs {
+    () => {};
+}
+
+#[cfg(not(feature = "qs"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __require_qs {
+    () => {
+        compile_error!(
+            "`query_encoding = Nested` requires the `qs` feature: `hadorn = { version = \"...\", features = [\"qs\"] }`"
+        );
+    };
+}
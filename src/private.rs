@@ -0,0 +1,119 @@
+mod codec;
+mod query;
+mod response;
+
+pub use codec::*;
+pub use query::*;
+pub use response::*;
+
+use crate::PathError;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+///
+/// the characters need to be encoded in a path segment
+///
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'\\')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+///
+/// the characters need to be encoded in a catch-all path, keep the `/`
+///
+const PATH_TAIL: &AsciiSet = &PATH_SEGMENT.remove(b'/');
+
+///
+/// the characters need to be encoded in a query name or value
+///
+const QUERY_COMPONENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'&')
+    .add(b'+')
+    .add(b'<')
+    .add(b'=')
+    .add(b'>')
+    .add(b'`');
+
+///
+/// display the value as a percent-encoded path segment
+///
+pub struct PathSegment<T>(pub T);
+
+impl<T: Display> Display for PathSegment<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let value = self.0.to_string();
+        Display::fmt(&utf8_percent_encode(&value, PATH_SEGMENT), f)
+    }
+}
+
+///
+/// display the value as a percent-encoded catch-all path, the `/` is kept
+///
+pub struct PathTail<T>(pub T);
+
+impl<T: Display> Display for PathTail<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let value = self.0.to_string();
+        Display::fmt(&utf8_percent_encode(&value, PATH_TAIL), f)
+    }
+}
+
+///
+/// check the `#[path]` argument is not a `.` or `..` segment, the url parser resolves it even if it is encoded
+///
+pub fn path_segment<T: Display>(
+    client: &reqwest::Client,
+    name: &'static str,
+    value: T,
+) -> Result<PathSegment<String>, PathError> {
+    let value = value.to_string();
+    match is_dot_segment(&value) {
+        true => Err(PathError::new(client, name, value)),
+        false => Ok(PathSegment(value)),
+    }
+}
+
+///
+/// check the catch-all `#[path]` argument has no `.` or `..` segment
+///
+pub fn path_tail<T: Display>(
+    client: &reqwest::Client,
+    name: &'static str,
+    value: T,
+) -> Result<PathTail<String>, PathError> {
+    let value = value.to_string();
+    match value.split('/').any(is_dot_segment) {
+        true => Err(PathError::new(client, name, value)),
+        false => Ok(PathTail(value)),
+    }
+}
+
+fn is_dot_segment(segment: &str) -> bool {
+    matches!(segment, "." | "..")
+}
+
+///
+/// display the value as a percent-encoded query name or value
+///
+pub struct QueryComponent<T>(pub T);
+
+impl<T: Display> Display for QueryComponent<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let value = self.0.to_string();
+        Display::fmt(&utf8_percent_encode(&value, QUERY_COMPONENT), f)
+    }
+}
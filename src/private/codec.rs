@@ -0,0 +1,81 @@
+use crate::Decoder;
+use reqwest::{RequestBuilder, Response};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Display;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+///
+/// bind the decoder with the decoded type, so the `Accept` header can be set before sending the request
+///
+pub struct DecoderOf<D, T>(PhantomData<fn() -> (D, T)>);
+
+pub fn decoder<D: Decoder<T>, T>() -> DecoderOf<D, T> {
+    DecoderOf(PhantomData)
+}
+
+impl<D: Decoder<T>, T> DecoderOf<D, T> {
+    pub fn accept(&self, request: RequestBuilder) -> RequestBuilder {
+        match D::ACCEPT {
+            Some(accept) => request.header(http::header::ACCEPT, accept),
+            None => request,
+        }
+    }
+
+    pub fn decode(&self, response: Response) -> impl Future<Output = Result<T, D::Error>> {
+        D::decode(response)
+    }
+}
+
+///
+/// report the encode error of the codecs not built in `reqwest` same as `Json`: the request fails to build,
+/// the `send` returns the builder error
+///
+pub fn encode_error(request: RequestBuilder, error: impl Display) -> RequestBuilder {
+    request.json(&Rejected(error.to_string()))
+}
+
+///
+/// a builder error with the message, keep the errors before building the request compatible with `reqwest::Result`
+///
+pub fn builder_error(client: &reqwest::Client, error: impl Display) -> reqwest::Error {
+    // any valid url, the request fails on the rejected body
+    let request = encode_error(client.get("http://localhost/"), error);
+    match request.build() {
+        Err(e) => e,
+        Ok(_) => unreachable!("the rejected body never encodes"),
+    }
+}
+
+///
+/// report the decode error of the codecs not built in `reqwest` same as `Json`: a decode `reqwest::Error`
+///
+pub fn decode_error(error: impl Display) -> reqwest::Error {
+    let body = serde_json::to_vec(&error.to_string()).unwrap_or_default();
+    let response = Response::from(http::Response::new(body));
+    // the body is in memory, the decoding is ready at the first poll
+    match pin!(response.json::<Rejected>()).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(Err(e)) => e,
+        _ => unreachable!("the rejected body never decodes"),
+    }
+}
+
+///
+/// always fails to serialize or deserialize with the message
+///
+struct Rejected(String);
+
+impl Serialize for Rejected {
+    fn serialize<S: Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+        Err(serde::ser::Error::custom(&self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for Rejected {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let message = String::deserialize(deserializer)?;
+        Err(serde::de::Error::custom(message))
+    }
+}
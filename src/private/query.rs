@@ -0,0 +1,354 @@
+use serde::ser::{Error, Impossible, SerializeSeq};
+use serde::{Serialize, Serializer};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::fmt::Display;
+
+///
+/// the collection style of the query argument
+///
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum QueryStyle {
+    /// repeated keys: `ids=1&ids=2`
+    Form,
+    /// comma-separated values: `ids=1,2`
+    Csv,
+    /// bracketed keys: `ids[]=1&ids[]=2`
+    Brackets,
+    /// pipe-delimited values: `ids=1|2`
+    Pipes,
+}
+
+///
+/// the collection types supported by the styled query argument
+///
+pub trait Collection {
+    type Item;
+
+    fn items(&self) -> impl Iterator<Item = &Self::Item>;
+}
+
+impl<T> Collection for [T] {
+    type Item = T;
+
+    fn items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+}
+
+impl<T, const N: usize> Collection for [T; N] {
+    type Item = T;
+
+    fn items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+}
+
+impl<T> Collection for Vec<T> {
+    type Item = T;
+
+    fn items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+}
+
+impl<T> Collection for VecDeque<T> {
+    type Item = T;
+
+    fn items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+}
+
+impl<T, S> Collection for HashSet<T, S> {
+    type Item = T;
+
+    fn items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+}
+
+impl<T> Collection for BTreeSet<T> {
+    type Item = T;
+
+    fn items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter()
+    }
+}
+
+impl<C: Collection + ?Sized> Collection for &C {
+    type Item = C::Item;
+
+    fn items(&self) -> impl Iterator<Item = &Self::Item> {
+        (**self).items()
+    }
+}
+
+impl<C: Collection> Collection for Option<C> {
+    type Item = C::Item;
+
+    fn items(&self) -> impl Iterator<Item = &Self::Item> {
+        self.iter().flat_map(|collection| collection.items())
+    }
+}
+
+///
+/// serialize a collection argument as the query pairs with the style
+///
+pub struct StyledQuery<'a, C: ?Sized> {
+    name: &'a str,
+    values: &'a C,
+    style: QueryStyle,
+}
+
+impl<'a, C: ?Sized> StyledQuery<'a, C> {
+    pub fn new(name: &'a str, values: &'a C, style: QueryStyle) -> Self {
+        Self {
+            name,
+            values,
+            style,
+        }
+    }
+}
+
+impl<C> Serialize for StyledQuery<'_, C>
+where
+    C: Collection + ?Sized,
+    C::Item: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let separator = match self.style {
+            QueryStyle::Form => {
+                return serialize_pairs(serializer, self.name, self.values.items());
+            }
+            QueryStyle::Brackets => {
+                let name = format!("{}[]", self.name);
+                return serialize_pairs(serializer, &name, self.values.items());
+            }
+            QueryStyle::Csv => ",",
+            QueryStyle::Pipes => "|",
+        };
+        let values = self
+            .values
+            .items()
+            .map(|value| value.serialize(ValueSerializer))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(S::Error::custom)?;
+        match values.is_empty() {
+            true => serializer.serialize_seq(Some(0))?.end(),
+            false => serialize_pairs(serializer, self.name, [values.join(separator)].iter()),
+        }
+    }
+}
+
+fn serialize_pairs<'a, S, T>(
+    serializer: S,
+    name: &str,
+    values: impl Iterator<Item = &'a T>,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize + 'a,
+{
+    let mut seq = serializer.serialize_seq(None)?;
+    for value in values {
+        seq.serialize_element(&(name, value))?;
+    }
+    seq.end()
+}
+
+///
+/// the error of serialize a non-scalar value as a query value
+///
+#[derive(Debug)]
+struct ValueError(String);
+
+impl Display for ValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ValueError {}
+
+impl Error for ValueError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+///
+/// serialize a scalar value to string
+///
+struct ValueSerializer;
+
+macro_rules! serialize_display {
+    ($($method:ident: $ty:ty),* $(,)?) => {
+        $(
+            fn $method(self, value: $ty) -> Result<Self::Ok, Self::Error> {
+                Ok(value.to_string())
+            }
+        )*
+    };
+}
+
+impl Serializer for ValueSerializer {
+    type Ok = String;
+    type Error = ValueError;
+    type SerializeSeq = Impossible<String, ValueError>;
+    type SerializeTuple = Impossible<String, ValueError>;
+    type SerializeTupleStruct = Impossible<String, ValueError>;
+    type SerializeTupleVariant = Impossible<String, ValueError>;
+    type SerializeMap = Impossible<String, ValueError>;
+    type SerializeStruct = Impossible<String, ValueError>;
+    type SerializeStructVariant = Impossible<String, ValueError>;
+
+    serialize_display! {
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_char: char,
+        serialize_str: &str,
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(String::from_utf8_lossy(value).into_owned())
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(String::new())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(String::new())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(String::new())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(unsupported())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(unsupported())
+    }
+}
+
+fn unsupported() -> ValueError {
+    ValueError::custom("unsupported query value, only scalar values are allowed")
+}
+
+///
+/// serialize the query struct with the nested encoding: `filter[status]=open&filter[tags][0]=a`
+///
+pub struct NestedQuery<'a, T>(pub &'a T);
+
+#[cfg(feature = "qs")]
+impl<T: Serialize> Serialize for NestedQuery<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let query = serde_qs::to_string(self.0).map_err(S::Error::custom)?;
+        let decode = |value: &str| {
+            percent_encoding::percent_decode_str(&value.replace('+', " "))
+                .decode_utf8_lossy()
+                .into_owned()
+        };
+        let mut seq = serializer.serialize_seq(None)?;
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            seq.serialize_element(&(decode(name), decode(value)))?;
+        }
+        seq.end()
+    }
+}
+
+///
+/// the `qs` feature is disabled, the macro reports the compile error by `__require_qs!`
+///
+#[cfg(not(feature = "qs"))]
+impl<T> Serialize for NestedQuery<'_, T> {
+    fn serialize<S: Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+        Err(S::Error::custom("the `qs` feature is disabled"))
+    }
+}
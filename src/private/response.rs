@@ -0,0 +1,190 @@
+use crate::{Decoder, HeaderError, ProblemDetails, Response, StatusError};
+use http::header::CONTENT_TYPE;
+use http::{HeaderMap, Method, StatusCode, Version};
+use reqwest::Url;
+use std::any::Any;
+use std::error::Error;
+use std::str::FromStr;
+
+///
+/// the response parts kept before the body is consumed by the decoder
+///
+pub struct ResponseParts {
+    status: StatusCode,
+    version: Version,
+    headers: HeaderMap,
+    url: Url,
+}
+
+impl ResponseParts {
+    pub fn new(response: &reqwest::Response) -> Self {
+        Self {
+            status: response.status(),
+            version: response.version(),
+            headers: response.headers().clone(),
+            url: response.url().clone(),
+        }
+    }
+
+    pub fn into_response<T>(self, body: T) -> Response<T> {
+        Response {
+            status: self.status,
+            version: self.version,
+            headers: self.headers,
+            url: self.url,
+            body,
+        }
+    }
+}
+
+///
+/// the max length of the body snippet kept in the [`StatusError`]
+///
+const BODY_SNIPPET_LIMIT: usize = 1024;
+
+///
+/// the options of checking the response status
+///
+pub struct StatusCheck {
+    pub method: Method,
+    pub contract: &'static str,
+    pub problem_details: bool,
+    /// the inclusive status ranges accepted in addition to the success statuses
+    pub accept_status: &'static [(u16, u16)],
+}
+
+impl StatusCheck {
+    fn accepts(&self, status: StatusCode) -> bool {
+        let status = status.as_u16();
+        self.accept_status
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&status))
+    }
+}
+
+///
+/// check the response status, read a truncated body snippet into the [`StatusError`] if the status is not success
+///
+pub async fn check_status(
+    response: reqwest::Response,
+    check: StatusCheck,
+) -> Result<reqwest::Response, StatusError> {
+    match response.error_for_status_ref() {
+        Ok(_) => Ok(response),
+        Err(_) if check.accepts(response.status()) => Ok(response),
+        Err(source) => Err(status_error(response, check, source).await),
+    }
+}
+
+///
+/// same as [`check_status`], and decode the error body with the decoder `D` into the [`StatusError`]
+///
+pub async fn check_status_with_body<D, T>(
+    response: reqwest::Response,
+    check: StatusCheck,
+) -> Result<reqwest::Response, StatusError>
+where
+    D: Decoder<T>,
+    D::Error: Into<Box<dyn Error + Send + Sync>>,
+    T: Send + Sync + 'static,
+{
+    let source = match response.error_for_status_ref() {
+        Ok(_) => return Ok(response),
+        Err(_) if check.accepts(response.status()) => return Ok(response),
+        Err(source) => source,
+    };
+    let mut error = status_error(response, check, source).await;
+    let mut response = http::Response::new(error.bytes.clone());
+    *response.status_mut() = error.status;
+    *response.headers_mut() = error.headers.clone();
+    let error_body = D::decode(reqwest::Response::from(response))
+        .await
+        .map(|error_body| Box::new(error_body) as Box<dyn Any + Send + Sync>)
+        .map_err(Into::into);
+    error.error_body = Some(error_body);
+    Err(error)
+}
+
+async fn status_error(
+    response: reqwest::Response,
+    check: StatusCheck,
+    source: reqwest::Error,
+) -> StatusError {
+    let status = response.status();
+    let headers = response.headers().clone();
+    let url = response.url().clone();
+    let bytes = response.bytes().await.unwrap_or_default();
+    let problem_details = match check.problem_details && is_problem_details(&headers) {
+        true => serde_json::from_slice(&bytes).ok(),
+        false => None,
+    };
+    StatusError {
+        status,
+        headers,
+        body: snippet(&bytes),
+        bytes,
+        error_body: None,
+        problem_details,
+        method: check.method,
+        url,
+        contract: check.contract,
+        source,
+    }
+}
+
+///
+/// the response content type is `application/problem+json`, ignore the parameters
+///
+fn is_problem_details(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|value| {
+            value
+                .trim()
+                .eq_ignore_ascii_case(ProblemDetails::CONTENT_TYPE)
+        })
+}
+
+fn snippet(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    match text.char_indices().nth(BODY_SNIPPET_LIMIT) {
+        Some((index, _)) => format!("{}...", &text[..index]),
+        None => text.into_owned(),
+    }
+}
+
+///
+/// parse the required response header with `FromStr`
+///
+pub fn response_header<T>(headers: &HeaderMap, name: &'static str) -> Result<T, HeaderError>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Error + Send + Sync>>,
+{
+    optional_response_header(headers, name)?.ok_or_else(|| HeaderError::missing(name))
+}
+
+///
+/// parse the optional response header with `FromStr`, `None` if the header is missing
+///
+pub fn optional_response_header<T>(
+    headers: &HeaderMap,
+    name: &'static str,
+) -> Result<Option<T>, HeaderError>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn Error + Send + Sync>>,
+{
+    match headers.get(name) {
+        Some(value) => {
+            let value = value.to_str().map_err(|e| HeaderError::invalid(name, e))?;
+            value
+                .parse()
+                .map(Some)
+                .map_err(|e| HeaderError::invalid(name, e))
+        }
+        None => Ok(None),
+    }
+}
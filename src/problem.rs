@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+///
+/// the `application/problem+json` error response defined by [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807)
+///
+/// enable it with `#[hadorn(problem_details)]`, read it by `error.problem_details()`.
+///
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    ///
+    /// the `type` member, the problem type uri, `about:blank` if absent
+    ///
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+
+    ///
+    /// the short summary of the problem type
+    ///
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    ///
+    /// the http status code
+    ///
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+
+    ///
+    /// the explanation specific to this occurrence of the problem
+    ///
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+
+    ///
+    /// the uri identifies the specific occurrence of the problem
+    ///
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+
+    ///
+    /// the extension members
+    ///
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+impl ProblemDetails {
+    ///
+    /// the media type of the problem details
+    ///
+    pub const CONTENT_TYPE: &'static str = "application/problem+json";
+}
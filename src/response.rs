@@ -0,0 +1,76 @@
+use http::{HeaderMap, StatusCode, Version};
+use reqwest::Url;
+
+///
+/// the decoded response body with the response status, version, headers and url
+///
+/// use it as the method return type: `Result<hadorn::Response<T>>`, the body `T` is decoded with the configured decoder.
+///
+#[derive(Debug, Clone)]
+pub struct Response<T> {
+    pub(crate) status: StatusCode,
+    pub(crate) version: Version,
+    pub(crate) headers: HeaderMap,
+    pub(crate) url: Url,
+    pub(crate) body: T,
+}
+
+impl<T> Response<T> {
+    ///
+    /// the response status
+    ///
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    ///
+    /// the response http version
+    ///
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    ///
+    /// the response headers
+    ///
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    ///
+    /// the final url of the response
+    ///
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    ///
+    /// the decoded body
+    ///
+    pub fn body(&self) -> &T {
+        &self.body
+    }
+
+    ///
+    /// consume the response, return the decoded body
+    ///
+    pub fn into_body(self) -> T {
+        self.body
+    }
+
+    ///
+    /// map the decoded body, keep the others
+    ///
+    pub fn map<U, F>(self, f: F) -> Response<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Response {
+            status: self.status,
+            version: self.version,
+            headers: self.headers,
+            url: self.url,
+            body: f(self.body),
+        }
+    }
+}
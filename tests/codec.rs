@@ -0,0 +1,154 @@
+mod common;
+
+use common::Response;
+
+///
+/// start a local http server which responds the request body with the same content type,
+/// responds `406` if the `Accept` header not matches the content type
+///
+#[allow(dead_code)]
+async fn mirror() -> String {
+    common::serve(|request| {
+        let content_type = request.header("content-type").unwrap_or("-").to_string();
+        match request.header("accept") {
+            Some(accept) if accept == content_type => Response::new(200)
+                .header("content-type", &content_type)
+                .body(request.body),
+            _ => Response::new(406),
+        }
+    })
+    .await
+}
+
+#[cfg(feature = "xml")]
+mod xml {
+    use super::common;
+    use common::Response;
+    use hadorn::{hadorn, post};
+    use reqwest::Client;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Order {
+        id: u32,
+        item: String,
+    }
+
+    #[hadorn(serialized = Xml, deserialized = Xml)]
+    trait Orders {
+        #[post(path = "/orders")]
+        async fn create(#[body] order: &Order) -> hadorn::Result<Order>;
+    }
+
+    #[tokio::test]
+    async fn xml_roundtrip() {
+        let client = OrdersClient::new(Client::new()).with_base_url(super::mirror().await);
+        let order = Order {
+            id: 1,
+            item: "book".to_string(),
+        };
+        assert_eq!(client.create(&order).await.unwrap(), order);
+    }
+
+    #[tokio::test]
+    async fn xml_decode_error() {
+        let base_url = common::serve(|_| Response::new(200).body("<order><id>one")).await;
+        let client = OrdersClient::new(Client::new()).with_base_url(base_url);
+        let order = Order {
+            id: 1,
+            item: "book".to_string(),
+        };
+        assert!(client.create(&order).await.unwrap_err().is_decode());
+    }
+}
+
+#[cfg(feature = "msgpack")]
+mod msgpack {
+    use hadorn::{hadorn, post};
+    use reqwest::Client;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Metric {
+        name: String,
+        values: Vec<f64>,
+    }
+
+    #[hadorn(serialized = MsgPack, deserialized = MsgPack)]
+    trait Metrics {
+        #[post(path = "/metrics")]
+        async fn push(#[body] metric: &Metric) -> hadorn::Result<Metric>;
+    }
+
+    #[tokio::test]
+    async fn msgpack_roundtrip() {
+        let client = MetricsClient::new(Client::new()).with_base_url(super::mirror().await);
+        let metric = Metric {
+            name: "cpu".to_string(),
+            values: vec![0.5, 0.75],
+        };
+        assert_eq!(client.push(&metric).await.unwrap(), metric);
+    }
+}
+
+#[cfg(feature = "cbor")]
+mod cbor {
+    use hadorn::{hadorn, post};
+    use reqwest::Client;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Reading {
+        sensor: String,
+        celsius: i32,
+    }
+
+    #[hadorn(serialized = Cbor, deserialized = Cbor)]
+    trait Readings {
+        #[post(path = "/readings")]
+        async fn report(#[body] reading: &Reading) -> hadorn::Result<Reading>;
+    }
+
+    #[tokio::test]
+    async fn cbor_roundtrip() {
+        let client = ReadingsClient::new(Client::new()).with_base_url(super::mirror().await);
+        let reading = Reading {
+            sensor: "gateway-1".to_string(),
+            celsius: 21,
+        };
+        assert_eq!(client.report(&reading).await.unwrap(), reading);
+    }
+}
+
+#[cfg(feature = "protobuf")]
+mod protobuf {
+    use hadorn::{hadorn, post};
+    use reqwest::Client;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Event {
+        #[prost(string, tag = "1")]
+        kind: String,
+        #[prost(uint64, tag = "2")]
+        timestamp: u64,
+        #[prost(string, repeated, tag = "3")]
+        labels: Vec<String>,
+    }
+
+    #[hadorn(serialized = Protobuf, deserialized = Protobuf)]
+    trait Events {
+        #[post(path = "/events")]
+        async fn publish(#[body] event: &Event) -> hadorn::Result<Event>;
+    }
+
+    #[tokio::test]
+    async fn protobuf_roundtrip() {
+        let client = EventsClient::new(Client::new()).with_base_url(super::mirror().await);
+        let event = Event {
+            kind: "deploy".to_string(),
+            timestamp: 1_700_000_000,
+            labels: vec!["prod".to_string(), "eu".to_string()],
+        };
+        assert_eq!(client.publish(&event).await.unwrap(), event);
+    }
+}
@@ -0,0 +1,186 @@
+mod common;
+
+use common::Response;
+use hadorn::{get, hadorn, post, Hadorn};
+use reqwest::Client;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct User {
+    name: String,
+}
+
+#[hadorn]
+trait Users {
+    #[get(path = "/users/<id>")]
+    async fn get(#[path] id: u32) -> hadorn::Result<User>;
+
+    #[post(path = "/users/<id>")]
+    async fn legacy(#[path] id: u32) -> reqwest::Result<String>;
+}
+
+async fn client() -> UsersClient {
+    let base_url = common::serve(|request| match request.target.as_str() {
+        "/users/1" => Response::new(200).body(r#"{"name":"hadorn"}"#),
+        "/users/2" => Response::new(200).body("not json"),
+        _ => Response::new(404)
+            .header("x-request-id", "abc")
+            .body("x".repeat(2048)),
+    })
+    .await;
+    UsersClient::new(Client::new()).with_base_url(base_url)
+}
+
+#[tokio::test]
+async fn status_error() {
+    let client = client().await;
+    assert_eq!(client.get(1).await.unwrap().name, "hadorn");
+
+    let error = client.get(3).await.unwrap_err();
+    assert!(error.is_status());
+    assert_eq!(error.status(), Some(http::StatusCode::NOT_FOUND));
+    match error {
+        hadorn::Error::Status(error) => {
+            assert_eq!(error.contract(), "Users::get");
+            assert_eq!(error.method(), http::Method::GET);
+            assert!(error.url().as_str().ends_with("/users/3"));
+            assert_eq!(error.headers()["x-request-id"], "abc");
+            assert_eq!(error.body(), format!("{}...", "x".repeat(1024)));
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[tokio::test]
+async fn decode_and_transport_error() {
+    let client = client().await;
+    assert!(client.get(2).await.unwrap_err().is_decode());
+
+    let client = UsersClient::new(Client::new()).with_base_url("http://127.0.0.1:1");
+    assert!(client.get(1).await.unwrap_err().is_transport());
+}
+
+#[tokio::test]
+async fn reqwest_result_compatible() {
+    let client = client().await;
+    let error = client.legacy(3).await.unwrap_err();
+    assert_eq!(error.status(), Some(reqwest::StatusCode::NOT_FOUND));
+}
+
+#[derive(Debug)]
+enum ApiError {
+    NotFound,
+    Other(hadorn::Error),
+}
+
+impl hadorn::FromError for ApiError {
+    fn from_error(error: hadorn::Error) -> Self {
+        match error.status() {
+            Some(http::StatusCode::NOT_FOUND) => ApiError::NotFound,
+            _ => ApiError::Other(error),
+        }
+    }
+}
+
+#[hadorn(error = ApiError)]
+trait Accounts {
+    #[get(path = "/users/<id>")]
+    async fn get(#[path] id: u32) -> Result<User, ApiError>;
+
+    #[get(path = "/users/<id>", error = hadorn::Error)]
+    async fn raw(#[path] id: u32) -> hadorn::Result<User>;
+}
+
+#[tokio::test]
+async fn custom_error() {
+    let base_url = client().await.base_url().unwrap().to_string();
+    let client = AccountsClient::new(Client::new()).with_base_url(base_url);
+    assert_eq!(client.get(1).await.unwrap().name, "hadorn");
+    assert!(matches!(client.get(3).await, Err(ApiError::NotFound)));
+    assert!(matches!(client.get(2).await, Err(ApiError::Other(e)) if e.is_decode()));
+    assert!(client.raw(3).await.unwrap_err().is_status());
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct ErrorBody {
+    code: String,
+    message: String,
+}
+
+#[hadorn(error_body = ErrorBody)]
+trait Orders {
+    #[get(path = "/orders/<id>")]
+    async fn get(#[path] id: u32) -> hadorn::Result<String>;
+}
+
+#[tokio::test]
+async fn error_body() {
+    let base_url = common::serve(|request| match request.target.as_str() {
+        "/orders/1" => Response::new(404).body(r#"{"code":"not_found","message":"no order"}"#),
+        _ => Response::new(500).body("internal error"),
+    })
+    .await;
+    let client = OrdersClient::new(Client::new()).with_base_url(base_url);
+
+    let error = client.get(1).await.unwrap_err();
+    assert_eq!(error.status(), Some(http::StatusCode::NOT_FOUND));
+    assert_eq!(
+        error.error_body::<ErrorBody>(),
+        Some(&ErrorBody {
+            code: "not_found".to_string(),
+            message: "no order".to_string()
+        })
+    );
+
+    let error = match client.get(2).await.unwrap_err() {
+        hadorn::Error::Status(error) => error,
+        error => panic!("unexpected error: {}", error),
+    };
+    assert_eq!(error.status(), http::StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(error.error_body::<ErrorBody>().is_none());
+    assert!(error.error_body_error().is_some());
+    assert_eq!(error.bytes().as_ref(), b"internal error");
+}
+
+#[hadorn(problem_details)]
+trait Payments {
+    #[post(path = "/payments/<id>")]
+    async fn pay(#[path] id: u32) -> hadorn::Result<String>;
+}
+
+#[tokio::test]
+async fn problem_details() {
+    let base_url = common::serve(|request| match request.target.as_str() {
+        "/payments/1" => Response::new(403)
+            .header("content-type", "application/problem+json; charset=utf-8")
+            .body(
+                r#"{"type":"https://example.com/probs/out-of-credit","title":"You do not have enough credit.",
+                "status":403,"detail":"Your current balance is 30, but that costs 50.","instance":"/payments/1","balance":30}"#,
+            ),
+        _ => Response::new(403)
+            .header("content-type", "application/json")
+            .body(r#"{"title":"forbidden"}"#),
+    })
+    .await;
+    let client = PaymentsClient::new(Client::new()).with_base_url(base_url);
+
+    let error = client.pay(1).await.unwrap_err();
+    let problem = error.problem_details().unwrap();
+    assert_eq!(
+        problem.kind.as_deref(),
+        Some("https://example.com/probs/out-of-credit")
+    );
+    assert_eq!(
+        problem.title.as_deref(),
+        Some("You do not have enough credit.")
+    );
+    assert_eq!(problem.status, Some(403));
+    assert_eq!(
+        problem.detail.as_deref(),
+        Some("Your current balance is 30, but that costs 50.")
+    );
+    assert_eq!(problem.instance.as_deref(), Some("/payments/1"));
+    assert_eq!(problem.extensions["balance"], 30);
+
+    assert!(client.pay(2).await.unwrap_err().problem_details().is_none());
+}
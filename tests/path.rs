@@ -0,0 +1,127 @@
+mod common;
+
+use hadorn::{get, hadorn};
+use reqwest::{Client, Result};
+
+#[hadorn(deserialized = Text)]
+trait Files {
+    #[get(path = "/files/<name>")]
+    async fn get(#[path] name: &str) -> Result<String>;
+
+    #[get(path = "/raw/<path>")]
+    async fn raw(#[path(raw)] path: &str) -> Result<String>;
+
+    #[get(path = "/v<version>/files/<name>.<ext>/")]
+    async fn file(
+        #[path] version: u8,
+        #[path = "ext"] extension: &str,
+        #[path] name: &str,
+    ) -> Result<String>;
+
+    #[get(path = "/lit/{x}")]
+    async fn braces() -> Result<String>;
+
+    #[get(path = "/lit/{x}/<name>?q={y}")]
+    async fn braces_with_param(#[path] name: &str) -> Result<String>;
+
+    #[get(path = "/repos/<owner>/<repo>/contents/<path..>")]
+    async fn contents(
+        #[path] owner: &str,
+        #[path] repo: &str,
+        #[path] path: &str,
+    ) -> Result<String>;
+}
+
+#[hadorn(deserialized = Text)]
+trait Folders {
+    #[get(path = "/folders/<name>/<path..>")]
+    async fn get(#[path] name: &str, #[path] path: &str) -> hadorn::Result<String>;
+}
+
+async fn client() -> FilesClient {
+    FilesClient::new(Client::new()).with_base_url(common::echo().await)
+}
+
+#[tokio::test]
+async fn encode_path_param() {
+    let client = client().await;
+    assert_eq!(
+        client.get("a/b?c#d e").await.unwrap(),
+        "GET /files/a%2Fb%3Fc%23d%20e"
+    );
+    assert_eq!(
+        client.get("中文").await.unwrap(),
+        "GET /files/%E4%B8%AD%E6%96%87"
+    );
+    assert_eq!(client.get("a..b").await.unwrap(), "GET /files/a..b");
+
+    // the url parser resolves the dot segments even if they are encoded, reject them before sending
+    assert!(client.get("..").await.unwrap_err().is_builder());
+    assert!(client.get(".").await.unwrap_err().is_builder());
+    assert!(client
+        .contents("rust-lang", "rust", "../../a")
+        .await
+        .unwrap_err()
+        .is_builder());
+}
+
+#[tokio::test]
+async fn dot_segment_error() {
+    let client = FoldersClient::new(Client::new()).with_base_url(common::echo().await);
+    assert_eq!(
+        client.get("a", "b/..c/d.").await.unwrap(),
+        "GET /folders/a/b/..c/d."
+    );
+    let path_error = |error: hadorn::Error| match error {
+        hadorn::Error::Encode(error) => {
+            let error = error.downcast::<hadorn::PathError>().unwrap();
+            (error.name(), error.value().to_string())
+        }
+        error => panic!("unexpected error: {error}"),
+    };
+    assert_eq!(
+        path_error(client.get(".", "a").await.unwrap_err()),
+        ("name", ".".to_string())
+    );
+    assert_eq!(
+        path_error(client.get("a", "b/../c").await.unwrap_err()),
+        ("path", "b/../c".to_string())
+    );
+}
+
+#[tokio::test]
+async fn literal_braces() {
+    let client = client().await;
+    assert_eq!(client.braces().await.unwrap(), "GET /lit/%7Bx%7D");
+    assert_eq!(
+        client.braces_with_param("a").await.unwrap(),
+        "GET /lit/%7Bx%7D/a?q={y}"
+    );
+}
+
+#[tokio::test]
+async fn raw_path_param() {
+    let client = client().await;
+    assert_eq!(client.raw("a/b/c").await.unwrap(), "GET /raw/a/b/c");
+}
+
+#[tokio::test]
+async fn inline_path_params() {
+    let client = client().await;
+    assert_eq!(
+        client.file(2, "tar.gz", "a b").await.unwrap(),
+        "GET /v2/files/a%20b.tar.gz/"
+    );
+}
+
+#[tokio::test]
+async fn catch_all_path_param() {
+    let client = client().await;
+    assert_eq!(
+        client
+            .contents("rust-lang", "rust", "src/lib rs/中")
+            .await
+            .unwrap(),
+        "GET /repos/rust-lang/rust/contents/src/lib%20rs/%E4%B8%AD"
+    );
+}
@@ -0,0 +1,231 @@
+mod common;
+
+use hadorn::{get, hadorn};
+use reqwest::{Client, Result};
+use serde::Serialize;
+
+#[hadorn(deserialized = Text)]
+trait Search {
+    #[get(path = "/search?type=<kind>&sort=stars")]
+    async fn search(#[query] kind: &str, #[query = "q"] keyword: &str) -> Result<String>;
+
+    #[get(path = "/users/<user>/items?owner=<user>&tag=<tag>")]
+    async fn items(
+        #[path] user: &str,
+        #[optional]
+        #[query]
+        tag: &str,
+    ) -> Result<String>;
+
+    #[get(path = "/range?r=<a>-<b>-<a>")]
+    async fn range(
+        #[optional]
+        #[query]
+        a: &str,
+        #[query] b: u32,
+    ) -> Result<String>;
+}
+
+#[hadorn(deserialized = Text)]
+trait Styles {
+    #[get(path = "/styles")]
+    async fn styles(
+        #[query] form: Vec<u32>,
+        #[query(style = "csv")] csv: &[&str],
+        #[query(style = "brackets")] brackets: &[u32],
+        #[query(style = "pipes", rename = "p")] pipes: Vec<String>,
+    ) -> Result<String>;
+
+    #[get(path = "/empty")]
+    async fn empty(
+        #[optional]
+        #[query(style = "csv")]
+        ids: Vec<u32>,
+    ) -> Result<String>;
+}
+
+#[hadorn(deserialized = Text, query_style = "csv")]
+trait DefaultStyle {
+    #[get(path = "/styles")]
+    async fn styles(
+        #[query] page: u32,
+        #[query] ids: &[u32],
+        #[query(style = "form")] tags: &[&str],
+    ) -> Result<String>;
+}
+
+#[derive(Default, Serialize)]
+struct SearchFilter {
+    status: Option<&'static str>,
+    language: Option<&'static str>,
+    #[serde(rename = "min_stars")]
+    stars: Option<u32>,
+}
+
+#[hadorn(deserialized = Text)]
+trait Filters {
+    #[get(path = "/filters")]
+    async fn filters(
+        #[query] page: u32,
+        #[query(flatten)] filter: &SearchFilter,
+        #[optional]
+        #[query(flatten)]
+        extra: &SearchFilter,
+    ) -> Result<String>;
+}
+
+#[hadorn(deserialized = Text)]
+trait Defaults {
+    #[get(path = "/items?sort=<sort>")]
+    async fn items(
+        #[query(default = 100)] per_page: u32,
+        #[query(default = "stars")] sort: &str,
+        #[query(style = "csv", default = vec![1, 2])] ids: Vec<u32>,
+    ) -> Result<String>;
+}
+
+async fn client() -> SearchClient {
+    SearchClient::new(Client::new()).with_base_url(common::echo().await)
+}
+
+#[tokio::test]
+async fn template_query_params() {
+    let client = client().await;
+    assert_eq!(
+        client.search("repo&x=1", "hadorn").await.unwrap(),
+        "GET /search?type=repo%26x%3D1&sort=stars&q=hadorn"
+    );
+}
+
+#[tokio::test]
+async fn template_optional_query_params() {
+    let client = client().await;
+    assert_eq!(
+        client.items("a b", Some("rust")).await.unwrap(),
+        "GET /users/a%20b/items?owner=a%20b&tag=rust"
+    );
+    assert_eq!(
+        client.items("a", None).await.unwrap(),
+        "GET /users/a/items?owner=a"
+    );
+    assert_eq!(
+        client.range(Some("x"), 1).await.unwrap(),
+        "GET /range?r=x-1-x"
+    );
+    assert_eq!(client.range(None, 1).await.unwrap(), "GET /range");
+}
+
+#[tokio::test]
+async fn collection_styles() {
+    let client = StylesClient::new(Client::new()).with_base_url(common::echo().await);
+    assert_eq!(
+        client
+            .styles(
+                vec![1, 2],
+                &["a", "b c"],
+                &[3, 4],
+                vec!["x".into(), "y".into()]
+            )
+            .await
+            .unwrap(),
+        "GET /styles?form=1&form=2&csv=a%2Cb+c&brackets%5B%5D=3&brackets%5B%5D=4&p=x%7Cy"
+    );
+    assert_eq!(client.empty(None).await.unwrap(), "GET /empty");
+    assert_eq!(client.empty(Some(vec![])).await.unwrap(), "GET /empty");
+}
+
+#[tokio::test]
+async fn default_collection_style() {
+    let client = DefaultStyleClient::new(Client::new()).with_base_url(common::echo().await);
+    assert_eq!(
+        client.styles(1, &[1, 2], &["a", "b"]).await.unwrap(),
+        "GET /styles?page=1&ids=1%2C2&tags=a&tags=b"
+    );
+}
+
+#[tokio::test]
+async fn flatten_query_params() {
+    let client = FiltersClient::new(Client::new()).with_base_url(common::echo().await);
+    let filter = SearchFilter {
+        status: Some("open"),
+        stars: Some(10),
+        ..SearchFilter::default()
+    };
+    assert_eq!(
+        client.filters(1, &filter, None).await.unwrap(),
+        "GET /filters?page=1&status=open&min_stars=10"
+    );
+    let extra = SearchFilter {
+        language: Some("rust"),
+        ..SearchFilter::default()
+    };
+    assert_eq!(
+        client
+            .filters(2, &SearchFilter::default(), Some(&extra))
+            .await
+            .unwrap(),
+        "GET /filters?page=2&language=rust"
+    );
+}
+
+#[tokio::test]
+async fn default_query_params() {
+    let client = DefaultsClient::new(Client::new()).with_base_url(common::echo().await);
+    assert_eq!(
+        client.items(None, None, None).await.unwrap(),
+        "GET /items?sort=stars&per_page=100&ids=1%2C2"
+    );
+    assert_eq!(
+        client
+            .items(Some(10), Some("forks"), Some(vec![3]))
+            .await
+            .unwrap(),
+        "GET /items?sort=forks&per_page=10&ids=3"
+    );
+}
+
+#[cfg(feature = "qs")]
+mod nested {
+    use super::common;
+    use hadorn::{get, hadorn};
+    use reqwest::{Client, Result};
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Filter {
+        status: &'static str,
+        tags: Vec<&'static str>,
+    }
+
+    #[hadorn(deserialized = Text, query_encoding = Nested)]
+    trait Issues {
+        #[get(path = "/issues")]
+        async fn issues(
+            #[query] page: u32,
+            #[query] filter: &Filter,
+            #[query] sort: &BTreeMap<String, String>,
+        ) -> Result<String>;
+
+        #[get(path = "/issues", query_encoding = Flat)]
+        async fn flat(#[query] ids: &[u32]) -> Result<String>;
+    }
+
+    #[tokio::test]
+    async fn nested_query_params() {
+        let client = IssuesClient::new(Client::new()).with_base_url(common::echo().await);
+        let filter = Filter {
+            status: "open",
+            tags: vec!["a", "b c"],
+        };
+        let sort = BTreeMap::from_iter([("created".into(), "desc".into())]);
+        assert_eq!(
+            client.issues(1, &filter, &sort).await.unwrap(),
+            "GET /issues?page=1&filter%5Bstatus%5D=open&filter%5Btags%5D%5B0%5D=a&filter%5Btags%5D%5B1%5D=b+c&sort%5Bcreated%5D=desc"
+        );
+        assert_eq!(
+            client.flat(&[1, 2]).await.unwrap(),
+            "GET /issues?ids=1&ids=2"
+        );
+    }
+}
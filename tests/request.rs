@@ -0,0 +1,150 @@
+mod common;
+
+use common::Response;
+use hadorn::{get, hadorn, patch, post, request, Encoder};
+use reqwest::{Client, RequestBuilder, Result};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct User {
+    name: &'static str,
+}
+
+#[hadorn(serialized = Json, deserialized = Text)]
+trait Users {
+    #[post(path = "/users")]
+    async fn create(
+        #[optional]
+        #[header = "x-token"]
+        token: &str,
+        #[optional]
+        #[body]
+        user: &User,
+    ) -> Result<String>;
+}
+
+#[hadorn(deserialized = Text)]
+trait Headers {
+    #[get(path = "/headers")]
+    async fn language(
+        #[header(rename = "accept-language", default = "en")] language: &str,
+    ) -> Result<String>;
+}
+
+#[hadorn(deserialized = Text)]
+trait Methods {
+    #[patch(path = "/p")]
+    async fn patch() -> Result<String>;
+
+    #[request(method = "PROPFIND", path = "/d")]
+    async fn propfind() -> Result<String>;
+}
+
+struct Upper;
+
+impl Encoder<&str> for Upper {
+    type Error = reqwest::Error;
+
+    fn encode(request: RequestBuilder, body: &str) -> Result<RequestBuilder> {
+        Ok(request
+            .header("content-type", "text/upper")
+            .body(body.to_uppercase()))
+    }
+}
+
+#[hadorn(deserialized = Text)]
+trait Messages {
+    #[post(path = "/messages", serialized = Upper)]
+    async fn send(#[body] message: &str) -> Result<String>;
+}
+
+async fn client() -> UsersClient {
+    let base_url = common::serve(|request| {
+        Response::new(200).body(format!(
+            "{} {}",
+            request.header("x-token").unwrap_or("-"),
+            String::from_utf8_lossy(&request.body)
+        ))
+    })
+    .await;
+    UsersClient::new(Client::new()).with_base_url(base_url)
+}
+
+#[tokio::test]
+async fn optional_header_and_body() {
+    let client = client().await;
+    assert_eq!(
+        client
+            .create(Some("secret"), Some(&User { name: "hadorn" }))
+            .await
+            .unwrap(),
+        r#"secret {"name":"hadorn"}"#
+    );
+    assert_eq!(client.create(None, None).await.unwrap(), "- ");
+}
+
+#[tokio::test]
+async fn request_methods() {
+    let client = MethodsClient::new(Client::new()).with_base_url(common::echo().await);
+    assert_eq!(client.patch().await.unwrap(), "PATCH /p");
+    assert_eq!(client.propfind().await.unwrap(), "PROPFIND /d");
+}
+
+#[tokio::test]
+async fn default_header() {
+    let base_url = common::serve(|request| {
+        Response::new(200).body(request.header("accept-language").unwrap_or("-").to_string())
+    })
+    .await;
+    let client = HeadersClient::new(Client::new()).with_base_url(base_url);
+    assert_eq!(client.language(None).await.unwrap(), "en");
+    assert_eq!(client.language(Some("zh")).await.unwrap(), "zh");
+}
+
+#[tokio::test]
+async fn custom_encoder() {
+    let base_url = common::serve(|request| {
+        Response::new(200).body(format!(
+            "{} {}",
+            request.header("content-type").unwrap_or("-"),
+            String::from_utf8_lossy(&request.body)
+        ))
+    })
+    .await;
+    let client = MessagesClient::new(Client::new()).with_base_url(base_url);
+    assert_eq!(client.send("hello").await.unwrap(), "text/upper HELLO");
+}
+
+#[hadorn(timeout = "1h")]
+trait Exports {
+    #[get(path = "/exports", timeout = "100ms")]
+    async fn export() -> hadorn::Result<String>;
+}
+
+#[hadorn(timeout = "100ms")]
+trait Reports {
+    #[get(path = "/reports")]
+    async fn report() -> hadorn::Result<String>;
+}
+
+#[tokio::test]
+async fn timeout() {
+    // accept the connections but never respond
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut streams = vec![];
+        while let Ok((stream, _)) = listener.accept().await {
+            streams.push(stream);
+        }
+    });
+
+    let is_timeout = |error: hadorn::Error| match error {
+        hadorn::Error::Transport(error) => error.is_timeout(),
+        _ => false,
+    };
+    let client = ExportsClient::new(Client::new()).with_base_url(base_url.clone());
+    assert!(is_timeout(client.export().await.unwrap_err()));
+    let client = ReportsClient::new(Client::new()).with_base_url(base_url);
+    assert!(is_timeout(client.report().await.unwrap_err()));
+}
@@ -0,0 +1,255 @@
+mod common;
+
+use common::Response;
+use hadorn::{get, hadorn, put, Decoder};
+use reqwest::{Client, Result};
+
+///
+/// decode the `key=value` lines of the response body
+///
+struct Lines;
+
+impl Decoder<Vec<(String, String)>> for Lines {
+    type Error = reqwest::Error;
+
+    async fn decode(response: reqwest::Response) -> Result<Vec<(String, String)>> {
+        let text = response.text().await?;
+        Ok(text
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect())
+    }
+}
+
+#[hadorn(deserialized = Lines)]
+trait Settings {
+    #[get(path = "/settings")]
+    async fn list() -> Result<Vec<(String, String)>>;
+
+    #[get(path = "/settings", deserialized = Text)]
+    async fn raw() -> Result<String>;
+
+    #[get(path = "/settings")]
+    async fn text() -> Result<String>;
+}
+
+#[hadorn]
+trait Inferred {
+    #[get(path = "/numbers")]
+    async fn text() -> Result<String>;
+
+    #[get(path = "/numbers")]
+    async fn bytes() -> Result<bytes::Bytes>;
+
+    #[get(path = "/numbers")]
+    async fn json() -> Result<Vec<u32>>;
+
+    #[get(path = "/numbers")]
+    async fn ignore() -> Result<()>;
+
+    #[get(path = "/numbers")]
+    async fn raw() -> Result<reqwest::Response>;
+}
+
+#[tokio::test]
+async fn custom_decoder() {
+    let base_url = common::serve(|_| Response::new(200).body("theme=dark\nlang=en")).await;
+    let client = SettingsClient::new(Client::new()).with_base_url(base_url);
+    assert_eq!(
+        client.list().await.unwrap(),
+        vec![
+            ("theme".to_string(), "dark".to_string()),
+            ("lang".to_string(), "en".to_string())
+        ]
+    );
+    assert_eq!(client.raw().await.unwrap(), "theme=dark\nlang=en");
+    assert_eq!(client.text().await.unwrap(), "theme=dark\nlang=en");
+}
+
+#[tokio::test]
+async fn infer_decoder() {
+    let base_url = common::serve(|_| Response::new(200).body("[1,2,3]")).await;
+    let client = InferredClient::new(Client::new()).with_base_url(base_url);
+    assert_eq!(client.text().await.unwrap(), "[1,2,3]");
+    assert_eq!(client.bytes().await.unwrap().as_ref(), b"[1,2,3]");
+    assert_eq!(client.json().await.unwrap(), vec![1, 2, 3]);
+    client.ignore().await.unwrap();
+    assert_eq!(client.raw().await.unwrap().status(), 200);
+}
+
+#[hadorn]
+trait Files {
+    #[get(path = "/files/<name>")]
+    async fn get(#[path] name: &str) -> Result<hadorn::Response<Vec<u32>>>;
+
+    #[get(path = "/files/<name>")]
+    async fn text(#[path] name: &str) -> Result<hadorn::Response<String>>;
+
+    #[get(path = "/files/<name>")]
+    async fn head(#[path] name: &str) -> Result<hadorn::Response<()>>;
+}
+
+#[tokio::test]
+async fn response_wrapper() {
+    let base_url =
+        common::serve(|_| Response::new(201).header("etag", "\"v1\"").body("[1,2]")).await;
+    let client = FilesClient::new(Client::new()).with_base_url(base_url.clone());
+
+    let response = client.get("a.json").await.unwrap();
+    assert_eq!(response.status(), 201);
+    assert_eq!(response.headers()["etag"], "\"v1\"");
+    assert_eq!(
+        response.url().as_str(),
+        format!("{}/files/a.json", base_url)
+    );
+    assert_eq!(response.into_body(), vec![1, 2]);
+
+    assert_eq!(client.text("a.json").await.unwrap().body(), "[1,2]");
+    assert_eq!(client.head("a.json").await.unwrap().status(), 201);
+}
+
+mod api {
+    use hadorn::{get, hadorn};
+    use serde::Deserialize;
+
+    ///
+    /// a user envelope named `Response`, not the `hadorn::Response`
+    ///
+    #[derive(Debug, PartialEq, Deserialize)]
+    pub struct Response<T> {
+        pub data: T,
+    }
+
+    #[hadorn(deserialized = Json)]
+    pub trait Envelopes {
+        #[get(path = "/envelope")]
+        async fn get() -> reqwest::Result<Response<Vec<u32>>>;
+    }
+}
+
+#[hadorn]
+trait Envelopes {
+    #[get(path = "/envelope")]
+    async fn get() -> Result<api::Response<Vec<u32>>>;
+}
+
+#[tokio::test]
+async fn user_response_type() {
+    use api::Envelopes as _;
+
+    let base_url = common::serve(|_| Response::new(200).body(r#"{"data":[1,2]}"#)).await;
+    let client = EnvelopesClient::new(Client::new()).with_base_url(base_url.clone());
+    assert_eq!(client.get().await.unwrap().data, vec![1, 2]);
+    let client = api::EnvelopesClient::new(Client::new()).with_base_url(base_url);
+    assert_eq!(client.get().await.unwrap().data, vec![1, 2]);
+}
+
+#[hadorn]
+trait Items {
+    #[get(path = "/items")]
+    #[response_header = "x-total-count"]
+    #[response_header = "x-next-page"]
+    async fn list() -> hadorn::Result<(Vec<u32>, u64, Option<u32>)>;
+
+    #[response_header = "x-total-count"]
+    #[get(path = "/items")]
+    async fn count() -> hadorn::Result<((), u64)>;
+
+    #[get(path = "/items")]
+    #[response_header = "location"]
+    async fn location() -> hadorn::Result<hadorn::Response<(String, String)>>;
+
+    #[get(path = "/broken")]
+    #[response_header = "x-total-count"]
+    async fn broken() -> hadorn::Result<((), u64)>;
+
+    #[get(path = "/broken")]
+    #[response_header = "x-request-id"]
+    async fn missing() -> hadorn::Result<((), String)>;
+}
+
+#[tokio::test]
+async fn response_headers() {
+    let base_url = common::serve(|request| {
+        let response = Response::new(200).header("location", "/items/1");
+        match request.target.as_str() {
+            "/items" => response.header("x-total-count", "42").body("[1,2]"),
+            _ => response.header("x-total-count", "many").body("[]"),
+        }
+    })
+    .await;
+    let client = ItemsClient::new(Client::new()).with_base_url(base_url);
+    assert_eq!(client.list().await.unwrap(), (vec![1, 2], 42, None));
+    assert_eq!(client.count().await.unwrap(), ((), 42));
+    assert_eq!(
+        client.location().await.unwrap().into_body(),
+        ("[1,2]".to_string(), "/items/1".to_string())
+    );
+
+    let header_error = |error: hadorn::Error| {
+        assert!(error.is_decode());
+        let source = std::error::Error::source(&error).unwrap();
+        let error = source.downcast_ref::<hadorn::HeaderError>().unwrap();
+        (error.name(), error.is_missing())
+    };
+    assert_eq!(
+        header_error(client.broken().await.unwrap_err()),
+        ("x-total-count", false)
+    );
+    assert_eq!(
+        header_error(client.missing().await.unwrap_err()),
+        ("x-request-id", true)
+    );
+}
+
+#[hadorn]
+trait Lookups {
+    #[get(path = "/lookups/<id>")]
+    async fn find(#[path] id: u32) -> hadorn::Result<Option<Vec<u32>>>;
+
+    #[get(path = "/lookups/<id>")]
+    async fn text(#[path] id: u32) -> hadorn::Result<Option<String>>;
+
+    #[get(path = "/lookups/<id>")]
+    async fn bytes(#[path] id: u32) -> hadorn::Result<Option<bytes::Bytes>>;
+
+    #[put(path = "/lookups/<id>", accept_status = [409, 500..=599])]
+    async fn create(#[path] id: u32) -> hadorn::Result<Vec<u32>>;
+}
+
+#[tokio::test]
+async fn accept_status() {
+    let base_url = common::serve(|request| match request.target.as_str() {
+        "/lookups/1" => Response::new(200).body("[1]"),
+        "/lookups/2" => Response::new(204),
+        "/lookups/3" => Response::new(409).body("[3]"),
+        "/lookups/4" => Response::new(503).body("[4]"),
+        "/lookups/5" => Response::new(400).body("[5]"),
+        "/lookups/6" => Response::new(200).body("null"),
+        _ => Response::new(404).body("not found"),
+    })
+    .await;
+    let client = LookupsClient::new(Client::new()).with_base_url(base_url);
+    assert_eq!(client.find(1).await.unwrap(), Some(vec![1]));
+    assert_eq!(client.find(2).await.unwrap(), None);
+    assert_eq!(client.find(9).await.unwrap(), None);
+    assert_eq!(client.find(6).await.unwrap(), None);
+    assert!(client.find(5).await.unwrap_err().is_status());
+
+    // the `String` and `Bytes` bodies are not decoded by `serde`, the `null` body is kept
+    assert_eq!(client.text(1).await.unwrap().as_deref(), Some("[1]"));
+    assert_eq!(client.text(6).await.unwrap().as_deref(), Some("null"));
+    assert_eq!(client.text(2).await.unwrap(), None);
+    assert_eq!(client.text(9).await.unwrap(), None);
+    assert_eq!(
+        client.bytes(6).await.unwrap(),
+        Some(bytes::Bytes::from_static(b"null"))
+    );
+    assert_eq!(client.bytes(9).await.unwrap(), None);
+
+    assert_eq!(client.create(3).await.unwrap(), vec![3]);
+    assert_eq!(client.create(4).await.unwrap(), vec![4]);
+    assert!(client.create(5).await.unwrap_err().is_status());
+    assert!(client.create(9).await.unwrap_err().is_status());
+}
@@ -0,0 +1,8 @@
+///
+/// the compile errors of the macros, run with `TRYBUILD=overwrite` to update the `.stderr` files
+///
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
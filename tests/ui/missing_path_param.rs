@@ -0,0 +1,9 @@
+use hadorn::{get, hadorn};
+
+#[hadorn]
+trait Users {
+    #[get(path = "/users")]
+    async fn get(#[path] id: u32) -> reqwest::Result<String>;
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use hadorn::{get, hadorn};
+
+#[hadorn]
+trait Users {
+    #[get(pth = "/users")]
+    async fn list() -> reqwest::Result<String>;
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use hadorn::{get, hadorn};
+
+#[hadorn]
+trait Users {
+    #[get(path = "/users")]
+    async fn list(#[query(raw)] name: &str) -> reqwest::Result<String>;
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use hadorn::{get, hadorn};
+
+#[hadorn]
+trait Users {
+    #[get(path = "/users/<id>")]
+    async fn get() -> reqwest::Result<String>;
+}
+
+fn main() {}
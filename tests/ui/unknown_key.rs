@@ -0,0 +1,9 @@
+use hadorn::{get, hadorn};
+
+#[hadorn]
+trait Users {
+    #[get(path = "/users", retries = 3)]
+    async fn list() -> reqwest::Result<String>;
+}
+
+fn main() {}